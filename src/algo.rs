@@ -1,99 +1,462 @@
-use crate::{AnyGraph, Key, Kinship, Value, Vertex};
-use std::collections::{HashSet, VecDeque};
+use crate::shortest_path::{
+    astar, bellman_ford, bidirectional_astar, dijkstra, floyd_warshall, johnson, AStarResult,
+    AllPairsShortestPaths, ShortestPaths,
+};
+use crate::traversal::{Bfs, BfsResult, Dfs, DfsResult};
+use crate::visit::{
+    bfs_visit, bfs_visit_from, dfs_visit, dfs_visit_from, BfsEvent, Control, DfsEvent,
+};
+use crate::{AnyGraph, Cost, Edge, GraphError, Key, Kinship, Value, Vertex, Weight};
+use std::collections::hash_map::RandomState;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::BuildHasher;
+use std::ops::Sub;
 
 /// An interface describing all the algorithms that can be used on any kind of graphs.
-pub trait Algorithms<K, V>: AnyGraph<K, V> + Kinship<K, V>
+pub trait Algorithms<K, V, W = (), S = RandomState>:
+    AnyGraph<K, V, W> + Kinship<K, V, W, S>
 where
     K: Key,
     V: Value,
+    W: Weight,
+    S: BuildHasher + Default,
 {
     /// Execute a Broad Search First the return the discovered graph.
     /// There is no order in which the edges are treated.
+    /// Only the vertices reachable from the first vertex are discovered, see [`Algorithms::bfs_forest`]
+    /// to cover the whole graph.
     fn bfs(&self) -> Option<Self> {
-        return if self.vertices().is_empty() {
-            None
-        } else {
-            let first = self.vertices().first().unwrap().clone();
-            self.bfs_with_starting_vertex(&first)
-        };
+        let first = self.vertices_iter().next()?;
+        self.bfs_with_starting_vertex(first)
     }
 
     /// Execute a Broad Search First with a starting vertex the return the discovered graph.
     /// There is no order in which the edges are treated.
     fn bfs_with_starting_vertex(&self, starting_vertex: &Vertex<K, V>) -> Option<Self> {
-        if !self.vertices().contains(starting_vertex) {
+        if !self.contains_key(starting_vertex.key()) {
             return None;
         }
-        let cloned_graph = self.clone();
+        let (mut new_graph, _) = self.remove_all_edges()?;
         let mut queue: VecDeque<K> = VecDeque::new();
-        queue.push_back(starting_vertex.key().clone());
-
-        return if let Some((mut new_graph, _)) = cloned_graph.remove_all_edges() {
-            let successors = cloned_graph.successors_as_key_and_edges();
-            let mut flagged: HashSet<K> = HashSet::new();
-
-            flagged.insert(starting_vertex.key().clone());
+        let mut flagged: HashSet<K, S> = HashSet::default();
 
-            while !queue.is_empty() {
-                let current = queue.pop_front().unwrap();
-                let neighbours = successors.get(&current).unwrap();
+        queue.push_back(starting_vertex.key().clone());
+        flagged.insert(starting_vertex.key().clone());
 
-                for neighbour in neighbours {
-                    if !flagged.contains(neighbour.to()) {
-                        new_graph = new_graph.add_edge(*neighbour).unwrap();
-                        flagged.insert(neighbour.to().clone());
-                        queue.push_back(neighbour.to().clone());
-                    }
+        while let Some(current) = queue.pop_front() {
+            for neighbour in self.neighbors_iter(&current).unwrap() {
+                if !flagged.contains(neighbour) {
+                    new_graph = new_graph
+                        .add_edge(tree_edge(self, &current, neighbour))
+                        .unwrap();
+                    flagged.insert(neighbour.clone());
+                    queue.push_back(neighbour.clone());
                 }
             }
-            Some(new_graph)
-        } else {
-            return None;
-        };
+        }
+        Some(new_graph)
     }
 
     /// Execute a Deep Search First the return the discovered graph.
     /// There is no order in which the edges are treated.
+    /// Only the vertices reachable from the first vertex are discovered, see [`Algorithms::dfs_forest`]
+    /// to cover the whole graph.
     fn dfs(&self) -> Option<Self> {
-        return if self.vertices().is_empty() {
-            None
-        } else {
-            let first = self.vertices().first().unwrap().clone();
-            self.dfs_with_starting_vertex(&first)
-        };
+        let first = self.vertices_iter().next()?;
+        self.dfs_with_starting_vertex(first)
     }
 
     /// Execute a Deep Search First with a starting vertex the return the discovered graph.
     /// There is no order in which the edges are treated.
     fn dfs_with_starting_vertex(&self, starting_vertex: &Vertex<K, V>) -> Option<Self> {
-        if !self.vertices().contains(starting_vertex) {
+        if !self.contains_key(starting_vertex.key()) {
             return None;
         }
-        let cloned_graph = self.clone();
-        let mut stack: VecDeque<K> = VecDeque::new();
-        stack.push_back(starting_vertex.key().clone());
+        let (mut new_graph, _) = self.remove_all_edges()?;
+        let mut stack: Vec<K> = Vec::new();
+        let mut flagged: HashSet<K, S> = HashSet::default();
+
+        stack.push(starting_vertex.key().clone());
+        flagged.insert(starting_vertex.key().clone());
+
+        while let Some(current) = stack.pop() {
+            for neighbour in self.neighbors_iter(&current).unwrap() {
+                if !flagged.contains(neighbour) {
+                    new_graph = new_graph
+                        .add_edge(tree_edge(self, &current, neighbour))
+                        .unwrap();
+                    flagged.insert(neighbour.clone());
+                    stack.push(neighbour.clone());
+                }
+            }
+        }
+        Some(new_graph)
+    }
+
+    /// Execute a Breadth First Search from a starting key then return the order in which the keys
+    /// were visited, their depth and their parent,
+    /// or fail if the vertex doesn't exist.
+    /// Use [`Bfs`] to stop the search early.
+    /// Complexity: O(V + E).
+    fn bfs_from(&self, start: &K) -> Result<BfsResult<K, S>, GraphError<K>> {
+        if !self.contains_key(start) {
+            return Err(GraphError::VertexNotFound(start.clone()));
+        }
+        Ok(Bfs::new(self, start).into_result())
+    }
 
-        return if let Some((mut new_graph, _)) = cloned_graph.remove_all_edges() {
-            let successors = cloned_graph.successors_as_key_and_edges();
-            let mut flagged: HashSet<K> = HashSet::new();
+    /// Execute a Depth First Search from a starting key then return the orders in which the keys
+    /// were discovered and finished, and the time of these events,
+    /// or fail if the vertex doesn't exist.
+    /// Use [`Dfs`] to stop the search early.
+    /// Complexity: O(V + E).
+    fn dfs_from(&self, start: &K) -> Result<DfsResult<K, S>, GraphError<K>> {
+        if !self.contains_key(start) {
+            return Err(GraphError::VertexNotFound(start.clone()));
+        }
+        Ok(Dfs::new(self, start).into_result())
+    }
 
-            flagged.insert(starting_vertex.key().clone());
+    /// Execute a Breadth First Search from several starting keys at once then return the order in
+    /// which the keys were visited, their depth from the closest starting key and their parent,
+    /// or fail if a vertex doesn't exist.
+    /// Complexity: O(V + E).
+    fn multi_source_bfs(&self, starts: &[K]) -> Result<BfsResult<K, S>, GraphError<K>> {
+        if let Some(start) = starts.iter().find(|start| !self.contains_key(start)) {
+            return Err(GraphError::VertexNotFound(start.clone()));
+        }
+        Ok(Bfs::with_starts(self, starts).into_result())
+    }
 
-            while !stack.is_empty() {
-                let current = stack.pop_back().unwrap();
-                let neighbours = successors.get(&current).unwrap();
+    /// Execute a Broad Search First from every vertex not discovered yet, taken by increasing key,
+    /// then return the discovered forest and the index of the tree holding each key.
+    /// In an undirected graph the trees are the connected components.
+    /// Complexity: O(V log V + E).
+    fn bfs_forest(&self) -> Option<(Self, HashMap<K, usize, S>)> {
+        let (mut forest, _) = self.remove_all_edges()?;
+        let mut component: HashMap<K, usize, S> = HashMap::default();
+        let mut discovered: HashSet<K, S> = HashSet::default();
+        let mut tree_count = 0;
 
-                for neighbour in neighbours {
-                    if !flagged.contains(neighbour.to()) {
-                        new_graph = new_graph.add_edge(*neighbour).unwrap();
-                        flagged.insert(neighbour.to().clone());
-                        stack.push_back(neighbour.to().clone());
+        for root in sorted_keys(self) {
+            if discovered.contains(root) {
+                continue;
+            }
+            let id = tree_count;
+            tree_count += 1;
+            bfs_visit_from(self, std::iter::once(root), &mut discovered, &mut |event| {
+                match event {
+                    BfsEvent::Discover(key) => {
+                        component.insert(key.clone(), id);
+                    }
+                    BfsEvent::TreeEdge(key, neighbour) => {
+                        forest = forest.add_edge(tree_edge(self, key, neighbour)).unwrap();
                     }
+                    _ => {}
                 }
+                Control::Continue
+            });
+        }
+        Some((forest, component))
+    }
+
+    /// Execute a Deep Search First from every vertex not discovered yet, taken by increasing key,
+    /// then return the discovered forest and the index of the tree holding each key.
+    /// In an undirected graph the trees are the connected components.
+    /// Complexity: O(V log V + E).
+    fn dfs_forest(&self) -> Option<(Self, HashMap<K, usize, S>)> {
+        let (mut forest, _) = self.remove_all_edges()?;
+        let mut component: HashMap<K, usize, S> = HashMap::default();
+        let mut discovered: HashSet<K, S> = HashSet::default();
+        let mut finished: HashSet<K, S> = HashSet::default();
+        let mut tree_count = 0;
+
+        for root in sorted_keys(self) {
+            if discovered.contains(root) {
+                continue;
             }
-            Some(new_graph)
-        } else {
-            return None;
-        };
+            let id = tree_count;
+            tree_count += 1;
+            dfs_visit_from(self, root, &mut discovered, &mut finished, &mut |event| {
+                match event {
+                    DfsEvent::Discover(key) => {
+                        component.insert(key.clone(), id);
+                    }
+                    DfsEvent::TreeEdge(key, neighbour) => {
+                        forest = forest.add_edge(tree_edge(self, key, neighbour)).unwrap();
+                    }
+                    _ => {}
+                }
+                Control::Continue
+            });
+        }
+        Some((forest, component))
+    }
+
+    /// Execute a Breadth First Search from a starting key, giving each event met to a visitor,
+    /// then return [`Control::Break`] if the visitor stopped the search or [`Control::Continue`] otherwise,
+    /// or fail if the vertex doesn't exist.
+    /// See [`bfs_visit`] for any other graph implementing [`Kinship`].
+    /// Complexity: O(V + E).
+    fn bfs_visit<F>(&self, start: &K, visitor: F) -> Result<Control, GraphError<K>>
+    where
+        F: FnMut(BfsEvent<K>) -> Control,
+    {
+        bfs_visit(self, start, visitor)
+    }
+
+    /// Execute a Depth First Search from a starting key, giving each event met to a visitor,
+    /// then return [`Control::Break`] if the visitor stopped the search or [`Control::Continue`] otherwise,
+    /// or fail if the vertex doesn't exist.
+    /// See [`dfs_visit`] for any other graph implementing [`Kinship`].
+    /// Complexity: O(V + E).
+    fn dfs_visit<F>(&self, start: &K, visitor: F) -> Result<Control, GraphError<K>>
+    where
+        F: FnMut(DfsEvent<K>) -> Control,
+    {
+        dfs_visit(self, start, visitor)
+    }
+
+    /// Find the shortest paths from a starting key to every key it reaches, using the weights
+    /// of the edges as their cost,
+    /// or fail if the vertex doesn't exist or if a reached edge has a negative weight.
+    /// Every edge leaving a reached key is checked, even one that can't shorten any path,
+    /// so any negative edge reachable from the start fails the search.
+    /// Complexity: O((V + E) log V).
+    fn dijkstra(&self, start: &K) -> Result<ShortestPaths<K, W, S>, GraphError<K>>
+    where
+        W: Cost,
+    {
+        self.dijkstra_with(start, |_, _, weight| *weight)
+    }
+
+    /// Find the shortest paths from a starting key to every key it reaches, computing the cost of
+    /// each edge from the vertices at its ends and its weight,
+    /// or fail if the vertex doesn't exist or if a reached edge has a negative cost.
+    /// Every edge leaving a reached key is checked, even one that can't shorten any path,
+    /// so any negative edge reachable from the start fails the search.
+    /// Complexity: O((V + E) log V).
+    fn dijkstra_with<C, F>(
+        &self,
+        start: &K,
+        cost: F,
+    ) -> Result<ShortestPaths<K, C, S>, GraphError<K>>
+    where
+        C: Cost,
+        F: FnMut(&Vertex<K, V>, &Vertex<K, V>, &W) -> C,
+    {
+        if !self.contains_key(start) {
+            return Err(GraphError::VertexNotFound(start.clone()));
+        }
+        dijkstra(self, start, None, cost)
     }
+
+    /// Find the shortest path between 2 keys, using the weights of the edges as their cost,
+    /// then return its keys, both ends included, and its cost, or [`None`] if the goal isn't reached,
+    /// or fail if a vertex doesn't exist or if a reached edge has a negative weight.
+    /// The search stops as soon as the goal is reached, so only the edges leaving the keys settled
+    /// before it are checked: every key closer than the goal is, the ones as close may not be.
+    /// Complexity: O((V + E) log V).
+    fn dijkstra_to(&self, start: &K, goal: &K) -> Result<Option<(Vec<K>, W)>, GraphError<K>>
+    where
+        W: Cost,
+    {
+        self.dijkstra_to_with(start, goal, |_, _, weight| *weight)
+    }
+
+    /// Find the shortest path between 2 keys, computing the cost of each edge from the vertices
+    /// at its ends and its weight, then return its keys, both ends included, and its cost,
+    /// or [`None`] if the goal isn't reached,
+    /// or fail if a vertex doesn't exist or if a reached edge has a negative cost.
+    /// The search stops as soon as the goal is reached, so only the edges leaving the keys settled
+    /// before it are checked: every key closer than the goal is, the ones as close may not be.
+    /// Complexity: O((V + E) log V).
+    fn dijkstra_to_with<C, F>(
+        &self,
+        start: &K,
+        goal: &K,
+        cost: F,
+    ) -> Result<Option<(Vec<K>, C)>, GraphError<K>>
+    where
+        C: Cost,
+        F: FnMut(&Vertex<K, V>, &Vertex<K, V>, &W) -> C,
+    {
+        if !self.contains_key(start) {
+            return Err(GraphError::VertexNotFound(start.clone()));
+        }
+        if !self.contains_key(goal) {
+            return Err(GraphError::VertexNotFound(goal.clone()));
+        }
+        let paths = dijkstra(self, start, Some(goal), cost)?;
+
+        Ok(paths.path_to(goal).map(|path| (path, paths.distance[goal])))
+    }
+
+    /// Find the shortest paths from a starting key to every key it reaches with the Bellman-Ford
+    /// algorithm, using the weights of the edges as their cost, which may be negative,
+    /// or fail if the vertex doesn't exist or with the keys of a negative cycle it reaches.
+    /// In an undirected graph an edge with a negative weight is a negative cycle on its own.
+    /// Complexity: O(V * E).
+    fn bellman_ford(&self, start: &K) -> Result<ShortestPaths<K, W, S>, GraphError<K>>
+    where
+        W: Cost,
+    {
+        self.bellman_ford_with(start, |_, _, weight| *weight)
+    }
+
+    /// Find the shortest paths from a starting key to every key it reaches with the Bellman-Ford
+    /// algorithm, computing the cost of each edge, which may be negative, from the vertices at its
+    /// ends and its weight,
+    /// or fail if the vertex doesn't exist or with the keys of a negative cycle it reaches.
+    /// Complexity: O(V * E).
+    fn bellman_ford_with<C, F>(
+        &self,
+        start: &K,
+        cost: F,
+    ) -> Result<ShortestPaths<K, C, S>, GraphError<K>>
+    where
+        C: Cost,
+        F: FnMut(&Vertex<K, V>, &Vertex<K, V>, &W) -> C,
+    {
+        if !self.contains_key(start) {
+            return Err(GraphError::VertexNotFound(start.clone()));
+        }
+        bellman_ford(self, std::iter::once(start), cost)
+    }
+
+    /// Find the cheapest path between 2 keys with the A* algorithm, computing the cost of each edge
+    /// from the vertices at its ends and its weight, and guided by a heuristic estimating the cost
+    /// from a vertex to the goal, given second,
+    /// then return the path with its cost and the number of keys expanded,
+    /// or fail if a vertex doesn't exist or if a reached edge has a negative cost.
+    /// The path is the cheapest one as long as the heuristic never overestimates the cost.
+    /// Complexity: O((V + E) log V) with a consistent heuristic.
+    fn astar<C, F, H>(
+        &self,
+        start: &K,
+        goal: &K,
+        cost: F,
+        heuristic: H,
+    ) -> Result<AStarResult<K, C>, GraphError<K>>
+    where
+        C: Cost,
+        F: FnMut(&Vertex<K, V>, &Vertex<K, V>, &W) -> C,
+        H: FnMut(&Vertex<K, V>, &Vertex<K, V>) -> C,
+    {
+        if !self.contains_key(start) {
+            return Err(GraphError::VertexNotFound(start.clone()));
+        }
+        if !self.contains_key(goal) {
+            return Err(GraphError::VertexNotFound(goal.clone()));
+        }
+        astar(self, start, goal, cost, heuristic)
+    }
+
+    /// Find the cheapest path between 2 keys with a bidirectional A* algorithm, searching forward
+    /// from the start and backward from the goal at the same time, computing the cost of each edge
+    /// from the vertices at its ends and its weight, and guided by a heuristic estimating the cost
+    /// from the first vertex given to the second one,
+    /// then return the path with its cost and the number of keys expanded by both searches,
+    /// or fail if a vertex doesn't exist or if a reached edge has a negative cost.
+    /// The path is the cheapest one as long as the heuristic is consistent: it never
+    /// overestimates the cost, and it never decreases by more than the cost of an edge along it.
+    /// Complexity: O((V + E) log V).
+    fn bidirectional_astar<C, F, H>(
+        &self,
+        start: &K,
+        goal: &K,
+        cost: F,
+        heuristic: H,
+    ) -> Result<AStarResult<K, C>, GraphError<K>>
+    where
+        C: Cost,
+        F: FnMut(&Vertex<K, V>, &Vertex<K, V>, &W) -> C,
+        H: FnMut(&Vertex<K, V>, &Vertex<K, V>) -> C,
+    {
+        if !self.contains_key(start) {
+            return Err(GraphError::VertexNotFound(start.clone()));
+        }
+        if !self.contains_key(goal) {
+            return Err(GraphError::VertexNotFound(goal.clone()));
+        }
+        bidirectional_astar(self, start, goal, cost, heuristic)
+    }
+
+    /// Find the shortest paths between every pair of keys with the Floyd-Warshall algorithm,
+    /// using the weights of the edges as their cost, which may be negative,
+    /// or fail with the keys of a negative cycle if any.
+    /// It suits dense graphs, see [`Algorithms::johnson`] for sparse ones.
+    /// Complexity: O(V³).
+    fn floyd_warshall(&self) -> Result<AllPairsShortestPaths<K, W, S>, GraphError<K>>
+    where
+        W: Cost,
+    {
+        self.floyd_warshall_with(|_, _, weight| *weight)
+    }
+
+    /// Find the shortest paths between every pair of keys with the Floyd-Warshall algorithm,
+    /// computing the cost of each edge, which may be negative, from the vertices at its ends and
+    /// its weight,
+    /// or fail with the keys of a negative cycle if any.
+    /// Complexity: O(V³).
+    fn floyd_warshall_with<C, F>(
+        &self,
+        cost: F,
+    ) -> Result<AllPairsShortestPaths<K, C, S>, GraphError<K>>
+    where
+        C: Cost,
+        F: FnMut(&Vertex<K, V>, &Vertex<K, V>, &W) -> C,
+    {
+        floyd_warshall(self, cost)
+    }
+
+    /// Find the shortest paths between every pair of keys with Johnson's algorithm,
+    /// using the weights of the edges as their cost, which may be negative,
+    /// or fail with the keys of a negative cycle if any.
+    /// It suits sparse graphs, see [`Algorithms::floyd_warshall`] for dense ones.
+    /// Complexity: O(V * E log V).
+    fn johnson(&self) -> Result<AllPairsShortestPaths<K, W, S>, GraphError<K>>
+    where
+        W: Cost + Sub<Output = W>,
+    {
+        self.johnson_with(|_, _, weight| *weight)
+    }
+
+    /// Find the shortest paths between every pair of keys with Johnson's algorithm,
+    /// computing the cost of each edge, which may be negative, from the vertices at its ends and
+    /// its weight,
+    /// or fail with the keys of a negative cycle if any.
+    /// Complexity: O(V * E log V).
+    fn johnson_with<C, F>(&self, cost: F) -> Result<AllPairsShortestPaths<K, C, S>, GraphError<K>>
+    where
+        C: Cost + Sub<Output = C>,
+        F: FnMut(&Vertex<K, V>, &Vertex<K, V>, &W) -> C,
+    {
+        johnson(self, cost)
+    }
+}
+
+/// Get the keys of a graph by increasing order.
+fn sorted_keys<G, K, V, W>(graph: &G) -> Vec<&K>
+where
+    G: AnyGraph<K, V, W>,
+    K: Key,
+    V: Value,
+    W: Weight,
+{
+    let mut keys: Vec<&K> = graph.keys().collect();
+    keys.sort();
+    keys
+}
+
+/// Get a copy of the edge followed from a key to one of its neighbors.
+fn tree_edge<G, K, V, W>(graph: &G, key: &K, neighbour: &K) -> Edge<K, W>
+where
+    G: AnyGraph<K, V, W>,
+    K: Key,
+    V: Value,
+    W: Weight,
+{
+    let weight = graph.edge_weight(key, neighbour).unwrap();
+    Edge::with_weight(key.clone(), neighbour.clone(), weight.clone())
 }
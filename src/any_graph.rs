@@ -1,58 +1,281 @@
-use crate::types::{Key, Value, Vertex};
-use crate::Edge;
+use crate::types::{Key, Value, Vertex, Weight};
+use crate::{Edge, GraphError};
 
 /// An interface used to describe any kind of graph.
 ///
+/// Every fallible operation comes in 2 flavours: a `try_` method returning a [`GraphError`]
+/// describing why the operation failed, and a method returning an [`Option`].
+///
 /// # Generic implementations
 /// K describe a type of [`Key`] to use.
 /// V describe a type of [`Value`] to store.
-pub trait AnyGraph<K, V>: Sized + Clone + PartialEq
+/// W describe a type of [`Weight`] carried by the edges.
+#[allow(clippy::type_complexity)]
+pub trait AnyGraph<K, V, W = ()>: Sized + Clone + PartialEq
 where
     K: Key,
     V: Value,
+    W: Weight,
 {
     /// Get the vertices of the graph.
-    fn vertices(&self) -> Vec<Vertex<K, V>>;
+    fn vertices(&self) -> Vec<Vertex<K, V>> {
+        self.vertices_iter().cloned().collect()
+    }
+
+    /// Iterate over the vertices of the graph without copying them.
+    fn vertices_iter<'a>(&'a self) -> impl ExactSizeIterator<Item = &'a Vertex<K, V>>
+    where
+        K: 'a,
+        V: 'a;
+
+    /// Iterate over the keys of the vertices of the graph.
+    fn keys<'a>(&'a self) -> impl ExactSizeIterator<Item = &'a K>
+    where
+        K: 'a;
 
     /// Get the edges of the graph.
-    fn edges(&self) -> Vec<Edge<K>>;
+    fn edges(&self) -> Vec<Edge<K, W>> {
+        self.edges_iter().cloned().collect()
+    }
+
+    /// Iterate over the edges of the graph without copying them.
+    fn edges_iter<'a>(&'a self) -> impl ExactSizeIterator<Item = &'a Edge<K, W>>
+    where
+        K: 'a,
+        W: 'a;
+
+    /// Get the vertex of a given key.
+    fn get_vertex(&self, key: &K) -> Option<&Vertex<K, V>>;
+
+    /// Check if there is a vertex with a given key.
+    fn contains_key(&self, key: &K) -> bool {
+        self.get_vertex(key).is_some()
+    }
+
+    /// Check if there is an edge between 2 keys.
+    fn contains_edge(&self, key_from: &K, key_to: &K) -> bool {
+        self.edge_weight(key_from, key_to).is_some()
+    }
+
+    /// Get the number of vertices of the graph.
+    fn vertex_count(&self) -> usize;
+
+    /// Get the number of edges of the graph.
+    fn edge_count(&self) -> usize;
+
+    /// Get the number of edges to a given key, or [`None`] if the vertex doesn't exist.
+    fn in_degree(&self, key: &K) -> Option<usize>;
+
+    /// Get the number of edges from a given key, or [`None`] if the vertex doesn't exist.
+    fn out_degree(&self, key: &K) -> Option<usize>;
+
+    /// Get the number of edges touching a given key, a loop counting twice,
+    /// or [`None`] if the vertex doesn't exist.
+    fn degree(&self, key: &K) -> Option<usize> {
+        Some(self.in_degree(key)? + self.out_degree(key)?)
+    }
 
     /// Add a new vertex then return the graph.
-    fn add_vertex(&self, vertex: Vertex<K, V>) -> Option<Self>;
+    fn add_vertex(&self, vertex: Vertex<K, V>) -> Option<Self> {
+        self.try_add_vertex(vertex).ok()
+    }
+
+    /// Add a new vertex then return the graph, or fail if the key is already used.
+    fn try_add_vertex(&self, vertex: Vertex<K, V>) -> Result<Self, GraphError<K>>;
 
     /// Remove a vertex then return the new graph, the deleted vertex and its edges.
-    fn remove_vertex(&self, vertex: &Vertex<K, V>) -> Option<(Self, Vertex<K, V>, Vec<Edge<K>>)>;
+    fn remove_vertex(
+        &self,
+        vertex: &Vertex<K, V>,
+    ) -> Option<(Self, Vertex<K, V>, Vec<Edge<K, W>>)> {
+        self.try_remove_vertex(vertex).ok()
+    }
+
+    /// Remove a vertex then return the new graph, the deleted vertex and its edges,
+    /// or fail if the vertex doesn't exist.
+    fn try_remove_vertex(
+        &self,
+        vertex: &Vertex<K, V>,
+    ) -> Result<(Self, Vertex<K, V>, Vec<Edge<K, W>>), GraphError<K>> {
+        self.try_remove_vertex_where_key(vertex.key().clone())
+    }
 
     /// Remove all vertices then return the new graph, the deleted vertices and all the edges.
-    fn remove_all_vertices(&self) -> Option<(Self, Vec<Vertex<K, V>>, Vec<Edge<K>>)>;
+    fn remove_all_vertices(&self) -> Option<(Self, Vec<Vertex<K, V>>, Vec<Edge<K, W>>)>;
 
     /// Remove a vertex by its key then return the new graph, the deleted vertex and its edges.
-    fn remove_vertex_where_key(&self, key: K) -> Option<(Self, Vertex<K, V>, Vec<Edge<K>>)>;
+    fn remove_vertex_where_key(&self, key: K) -> Option<(Self, Vertex<K, V>, Vec<Edge<K, W>>)> {
+        self.try_remove_vertex_where_key(key).ok()
+    }
+
+    /// Remove a vertex by its key then return the new graph, the deleted vertex and its edges,
+    /// or fail if the vertex doesn't exist.
+    fn try_remove_vertex_where_key(
+        &self,
+        key: K,
+    ) -> Result<(Self, Vertex<K, V>, Vec<Edge<K, W>>), GraphError<K>>;
+
+    /// Replace the value of the vertex of a given key then return the new graph and the old value.
+    fn update_vertex_value(&self, key: K, value: V) -> Option<(Self, V)> {
+        self.try_update_vertex_value(key, value).ok()
+    }
+
+    /// Replace the value of the vertex of a given key then return the new graph and the old value,
+    /// or fail if the vertex doesn't exist.
+    fn try_update_vertex_value(&self, key: K, value: V) -> Result<(Self, V), GraphError<K>>;
+
+    /// Compute a new value from the value of the vertex of a given key,
+    /// then return the new graph and the old value.
+    fn with_vertex_value<F>(&self, key: K, f: F) -> Option<(Self, V)>
+    where
+        F: FnOnce(&V) -> V,
+    {
+        self.try_with_vertex_value(key, f).ok()
+    }
+
+    /// Compute a new value from the value of the vertex of a given key,
+    /// then return the new graph and the old value, or fail if the vertex doesn't exist.
+    fn try_with_vertex_value<F>(&self, key: K, f: F) -> Result<(Self, V), GraphError<K>>
+    where
+        F: FnOnce(&V) -> V,
+    {
+        let value = match self.get_vertex(&key) {
+            Some(vertex) => f(vertex.value()),
+            None => return Err(GraphError::VertexNotFound(key)),
+        };
+        self.try_update_vertex_value(key, value)
+    }
+
+    /// Compute a new value for every vertex from its key and its value, then return the new graph.
+    fn map_vertex_values<F>(&self, f: F) -> Self
+    where
+        F: FnMut(&K, &V) -> V;
 
     /// Add a new edge then return the new graph.
-    fn add_edge(&self, edge: Edge<K>) -> Option<Self>;
+    fn add_edge(&self, edge: Edge<K, W>) -> Option<Self> {
+        self.try_add_edge(edge).ok()
+    }
+
+    /// Add a new edge then return the new graph,
+    /// or fail if an endpoint is missing or if the edge already exists.
+    fn try_add_edge(&self, edge: Edge<K, W>) -> Result<Self, GraphError<K>>;
+
+    /// Add a new edge between 2 keys with the default weight then return the new graph.
+    fn add_edge_between_keys(&self, key_from: K, key_to: K) -> Option<Self>
+    where
+        W: Default,
+    {
+        self.try_add_edge_between_keys(key_from, key_to).ok()
+    }
+
+    /// Add a new edge between 2 keys with the default weight then return the new graph,
+    /// or fail if an endpoint is missing or if the edge already exists.
+    fn try_add_edge_between_keys(&self, key_from: K, key_to: K) -> Result<Self, GraphError<K>>
+    where
+        W: Default,
+    {
+        self.try_add_edge(Edge::new(key_from, key_to))
+    }
+
+    /// Get the weight of the edge between 2 keys.
+    fn edge_weight(&self, key_from: &K, key_to: &K) -> Option<&W>;
 
-    /// Add a new edge between 2 keys then return the new graph.
-    fn add_edge_between_keys(&self, key_from: K, key_to: K) -> Option<Self>;
+    /// Replace the weight of the edge between 2 keys then return the new graph and the old weight.
+    fn update_edge_weight(&self, key_from: K, key_to: K, weight: W) -> Option<(Self, W)> {
+        self.try_update_edge_weight(key_from, key_to, weight).ok()
+    }
+
+    /// Replace the weight of the edge between 2 keys then return the new graph and the old weight,
+    /// or fail if the edge doesn't exist.
+    fn try_update_edge_weight(
+        &self,
+        key_from: K,
+        key_to: K,
+        weight: W,
+    ) -> Result<(Self, W), GraphError<K>>;
 
     /// Remove an existing edge then return the new graph and the deleted edge.
-    fn remove_edge(&self, edge: &Edge<K>) -> Option<(Self, Edge<K>)>;
+    fn remove_edge(&self, edge: &Edge<K, W>) -> Option<(Self, Edge<K, W>)> {
+        self.try_remove_edge(edge).ok()
+    }
+
+    /// Remove an existing edge then return the new graph and the deleted edge,
+    /// or fail if the edge doesn't exist.
+    fn try_remove_edge(&self, edge: &Edge<K, W>) -> Result<(Self, Edge<K, W>), GraphError<K>> {
+        self.try_remove_edge_where_keys(edge.from().clone(), edge.to().clone())
+    }
 
     /// Remove an existing edge by their keys, then return the new graph and the deleted edge.
-    fn remove_edge_where_keys(&self, key_from: K, key_to: K) -> Option<(Self, Edge<K>)>;
+    fn remove_edge_where_keys(&self, key_from: K, key_to: K) -> Option<(Self, Edge<K, W>)> {
+        self.try_remove_edge_where_keys(key_from, key_to).ok()
+    }
+
+    /// Remove an existing edge by their keys, then return the new graph and the deleted edge,
+    /// or fail if the edge doesn't exist.
+    fn try_remove_edge_where_keys(
+        &self,
+        key_from: K,
+        key_to: K,
+    ) -> Result<(Self, Edge<K, W>), GraphError<K>>;
 
     /// Remove all the edges then return the new graph and all the deleted edges.
-    fn remove_all_edges(&self) -> Option<(Self, Vec<Edge<K>>)>;
+    fn remove_all_edges(&self) -> Option<(Self, Vec<Edge<K, W>>)>;
 
     /// Remove all existing edges from or to a given vertex, then return the new graph and the deleted edges.
-    fn remove_all_edges_where_vertex(&self, vertex: &Vertex<K, V>) -> Option<(Self, Vec<Edge<K>>)>;
+    fn remove_all_edges_where_vertex(
+        &self,
+        vertex: &Vertex<K, V>,
+    ) -> Option<(Self, Vec<Edge<K, W>>)> {
+        self.try_remove_all_edges_where_vertex(vertex).ok()
+    }
+
+    /// Remove all existing edges from or to a given vertex, then return the new graph and the deleted edges,
+    /// or fail if the vertex doesn't exist.
+    fn try_remove_all_edges_where_vertex(
+        &self,
+        vertex: &Vertex<K, V>,
+    ) -> Result<(Self, Vec<Edge<K, W>>), GraphError<K>> {
+        self.try_remove_all_edges_where_key(vertex.key().clone())
+    }
 
     /// Remove all existing edges from or to a given key, then return the new graph and the deleted edges.
-    fn remove_all_edges_where_key(&self, key_from: K) -> Option<(Self, Vec<Edge<K>>)>;
+    fn remove_all_edges_where_key(&self, key_from: K) -> Option<(Self, Vec<Edge<K, W>>)> {
+        self.try_remove_all_edges_where_key(key_from).ok()
+    }
+
+    /// Remove all existing edges from or to a given key, then return the new graph and the deleted edges,
+    /// or fail if the vertex doesn't exist.
+    fn try_remove_all_edges_where_key(
+        &self,
+        key_from: K,
+    ) -> Result<(Self, Vec<Edge<K, W>>), GraphError<K>>;
 
     /// Remove all existing edges from a given vertex, then return the new graph and the deleted edges.
-    fn remove_all_edges_from_vertex(&self, vertex: &Vertex<K, V>) -> Option<(Self, Vec<Edge<K>>)>;
+    fn remove_all_edges_from_vertex(
+        &self,
+        vertex: &Vertex<K, V>,
+    ) -> Option<(Self, Vec<Edge<K, W>>)> {
+        self.try_remove_all_edges_from_vertex(vertex).ok()
+    }
+
+    /// Remove all existing edges from a given vertex, then return the new graph and the deleted edges,
+    /// or fail if the vertex doesn't exist.
+    fn try_remove_all_edges_from_vertex(
+        &self,
+        vertex: &Vertex<K, V>,
+    ) -> Result<(Self, Vec<Edge<K, W>>), GraphError<K>> {
+        self.try_remove_all_edges_from_key(vertex.key().clone())
+    }
 
     /// Remove all existing edges from a given key, then return the new graph and the deleted edges.
-    fn remove_all_edges_from_key(&self, key_from: K) -> Option<(Self, Vec<Edge<K>>)>;
+    fn remove_all_edges_from_key(&self, key_from: K) -> Option<(Self, Vec<Edge<K, W>>)> {
+        self.try_remove_all_edges_from_key(key_from).ok()
+    }
+
+    /// Remove all existing edges from a given key, then return the new graph and the deleted edges,
+    /// or fail if the vertex doesn't exist.
+    fn try_remove_all_edges_from_key(
+        &self,
+        key_from: K,
+    ) -> Result<(Self, Vec<Edge<K, W>>), GraphError<K>>;
 }
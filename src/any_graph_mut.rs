@@ -0,0 +1,121 @@
+use crate::types::{Key, Value, Vertex, Weight};
+use crate::{AnyGraph, Edge, GraphError};
+
+/// An interface used to modify any kind of graph in place.
+/// Each method is the in-place counterpart of an [`AnyGraph`] method and returns what was removed,
+/// or a [`GraphError`] describing why the graph was left untouched.
+///
+/// # Generic implementations
+/// K describe a type of [`Key`] to use.
+/// V describe a type of [`Value`] to store.
+/// W describe a type of [`Weight`] carried by the edges.
+#[allow(clippy::type_complexity)]
+pub trait AnyGraphMut<K, V, W = ()>: AnyGraph<K, V, W>
+where
+    K: Key,
+    V: Value,
+    W: Weight,
+{
+    /// Insert a new vertex, or fail if the key is already used.
+    fn insert_vertex(&mut self, vertex: Vertex<K, V>) -> Result<(), GraphError<K>>;
+
+    /// Remove a vertex then return the deleted vertex and its edges.
+    fn remove_vertex_mut(
+        &mut self,
+        vertex: &Vertex<K, V>,
+    ) -> Result<(Vertex<K, V>, Vec<Edge<K, W>>), GraphError<K>> {
+        self.remove_vertex_where_key_mut(vertex.key().clone())
+    }
+
+    /// Remove all vertices then return the deleted vertices and all the edges.
+    fn remove_all_vertices_mut(&mut self) -> (Vec<Vertex<K, V>>, Vec<Edge<K, W>>);
+
+    /// Remove a vertex by its key then return the deleted vertex and its edges.
+    fn remove_vertex_where_key_mut(
+        &mut self,
+        key: K,
+    ) -> Result<(Vertex<K, V>, Vec<Edge<K, W>>), GraphError<K>>;
+
+    /// Replace the value of the vertex of a given key then return the old value.
+    fn update_vertex_value_mut(&mut self, key: K, value: V) -> Result<V, GraphError<K>>;
+
+    /// Compute a new value from the value of the vertex of a given key then return the old value.
+    fn with_vertex_value_mut<F>(&mut self, key: K, f: F) -> Result<V, GraphError<K>>
+    where
+        F: FnOnce(&V) -> V,
+    {
+        let value = match self.get_vertex(&key) {
+            Some(vertex) => f(vertex.value()),
+            None => return Err(GraphError::VertexNotFound(key)),
+        };
+        self.update_vertex_value_mut(key, value)
+    }
+
+    /// Compute a new value for every vertex from its key and its value.
+    fn map_vertex_values_mut<F>(&mut self, f: F)
+    where
+        F: FnMut(&K, &V) -> V;
+
+    /// Insert a new edge, or fail if an endpoint is missing or if the edge already exists.
+    fn insert_edge(&mut self, edge: Edge<K, W>) -> Result<(), GraphError<K>>;
+
+    /// Insert a new edge between 2 keys with the default weight,
+    /// or fail if an endpoint is missing or if the edge already exists.
+    fn insert_edge_between_keys(&mut self, key_from: K, key_to: K) -> Result<(), GraphError<K>>
+    where
+        W: Default,
+    {
+        self.insert_edge(Edge::new(key_from, key_to))
+    }
+
+    /// Replace the weight of the edge between 2 keys then return the old weight.
+    fn update_edge_weight_mut(
+        &mut self,
+        key_from: K,
+        key_to: K,
+        weight: W,
+    ) -> Result<W, GraphError<K>>;
+
+    /// Remove an existing edge then return the deleted edge.
+    fn remove_edge_mut(&mut self, edge: &Edge<K, W>) -> Result<Edge<K, W>, GraphError<K>> {
+        self.remove_edge_where_keys_mut(edge.from().clone(), edge.to().clone())
+    }
+
+    /// Remove an existing edge by their keys, then return the deleted edge.
+    fn remove_edge_where_keys_mut(
+        &mut self,
+        key_from: K,
+        key_to: K,
+    ) -> Result<Edge<K, W>, GraphError<K>>;
+
+    /// Remove all the edges then return all the deleted edges.
+    fn remove_all_edges_mut(&mut self) -> Vec<Edge<K, W>>;
+
+    /// Remove all existing edges from or to a given vertex, then return the deleted edges.
+    fn remove_all_edges_where_vertex_mut(
+        &mut self,
+        vertex: &Vertex<K, V>,
+    ) -> Result<Vec<Edge<K, W>>, GraphError<K>> {
+        self.remove_all_edges_where_key_mut(vertex.key().clone())
+    }
+
+    /// Remove all existing edges from or to a given key, then return the deleted edges.
+    fn remove_all_edges_where_key_mut(
+        &mut self,
+        key_from: K,
+    ) -> Result<Vec<Edge<K, W>>, GraphError<K>>;
+
+    /// Remove all existing edges from a given vertex, then return the deleted edges.
+    fn remove_all_edges_from_vertex_mut(
+        &mut self,
+        vertex: &Vertex<K, V>,
+    ) -> Result<Vec<Edge<K, W>>, GraphError<K>> {
+        self.remove_all_edges_from_key_mut(vertex.key().clone())
+    }
+
+    /// Remove all existing edges from a given key, then return the deleted edges.
+    fn remove_all_edges_from_key_mut(
+        &mut self,
+        key_from: K,
+    ) -> Result<Vec<Edge<K, W>>, GraphError<K>>;
+}
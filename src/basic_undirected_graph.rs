@@ -1,263 +1,599 @@
 use crate::algo::Algorithms;
+use crate::any_graph::AnyGraph;
+use crate::any_graph_mut::AnyGraphMut;
+use crate::iter::Counted;
 use crate::kinship::Kinship;
-use crate::{AnyGraph, Edge, Key, Value, Vertex};
-use std::borrow::BorrowMut;
+use crate::types::{same_weights, Key, UndirectedEdge, Value, Vertex, Weight};
+use crate::{Edge, GraphError};
 use std::collections::hash_map::RandomState;
-use std::collections::{HashMap, HashSet};
+use std::hash::BuildHasher;
 
 /// A basic implementation of an undirected graph.
 /// It doesn't allow multiple edges but allow loops.
-#[derive(Clone, PartialEq)]
-pub struct BasicUndirectedGraph<K, V>
+/// The vertices and edges are stored in persistent hash maps, so every new graph returned by
+/// [`AnyGraph`] shares most of its structure with the graph it was created from.
+/// The hasher of these maps, [`RandomState`] by default, can be swapped with `S` for a faster
+/// or a fixed-seed one.
+/// Each vertex indexes the edges touching it by the key at their other end, so the edges of a
+/// vertex are found in O(degree).
+/// The edges are stored as [`UndirectedEdge`], so the order of the keys given when adding an edge
+/// doesn't matter: the edges returned always go from their smallest key to the other one, and
+/// two graphs built in different orders are equal.
+pub struct BasicUndirectedGraph<K, V, W = (), S = RandomState>
 where
     K: Key,
     V: Value,
+    W: Weight,
+    S: BuildHasher,
 {
-    vertices: HashSet<Vertex<K, V>>,
-    edges: HashSet<Edge<K>>,
+    vertices: im::HashMap<K, Vertex<K, V>, S>,
+    adjacency: im::HashMap<K, im::HashMap<K, UndirectedEdge<K, W>, S>, S>,
+    edge_count: usize,
 }
 
-impl<K, V> AnyGraph<K, V> for BasicUndirectedGraph<K, V>
+impl<K, V, W, S> AnyGraph<K, V, W> for BasicUndirectedGraph<K, V, W, S>
 where
     K: Key,
     V: Value,
+    W: Weight,
+    S: BuildHasher + Default,
 {
-    /// Get the vertices of the graph.
-    /// Complexity: O(1*).
-    fn vertices(&self) -> Vec<Vertex<K, V>> {
-        self.vertices.iter().cloned().collect()
+    /// Iterate over the vertices of the graph.
+    /// Complexity: O(1).
+    fn vertices_iter<'a>(&'a self) -> impl ExactSizeIterator<Item = &'a Vertex<K, V>>
+    where
+        K: 'a,
+        V: 'a,
+    {
+        self.vertices.values()
     }
 
-    /// Get the edges of the graph.
-    /// Complexity: O(1*).
-    fn edges(&self) -> Vec<Edge<K>> {
-        self.edges.iter().cloned().collect()
+    /// Iterate over the keys of the vertices of the graph.
+    /// Complexity: O(1).
+    fn keys<'a>(&'a self) -> impl ExactSizeIterator<Item = &'a K>
+    where
+        K: 'a,
+    {
+        self.vertices.keys()
     }
 
-    /// Add a new vertex then return the graph.
-    /// Complexity: O(1*).
-    fn add_vertex(&self, vertex: Vertex<K, V>) -> Option<Self> {
-        let mut new_graph = self.clone();
-        return if new_graph.vertices.borrow_mut().insert(vertex) {
-            Some(new_graph)
-        } else {
-            None
-        };
+    /// Iterate over the edges of the graph, each going from its smallest key to the other one.
+    /// Complexity: O(1).
+    fn edges_iter<'a>(&'a self) -> impl ExactSizeIterator<Item = &'a Edge<K, W>>
+    where
+        K: 'a,
+        W: 'a,
+    {
+        let edges = self.adjacency.iter().flat_map(|(key, incident_edges)| {
+            incident_edges
+                .iter()
+                .filter(move |(other_key, _)| key <= *other_key)
+                .map(|(_, edge)| edge.as_edge())
+        });
+        Counted::new(edges, self.edge_count)
     }
 
-    /// Remove a vertex then return the new graph, the deleted vertex and its edges.
-    /// Complexity: O(E).
-    fn remove_vertex(&self, vertex: &Vertex<K, V>) -> Option<(Self, Vertex<K, V>, Vec<Edge<K>>)> {
+    /// Get the vertex of a given key.
+    /// Complexity: O(log V).
+    fn get_vertex(&self, key: &K) -> Option<&Vertex<K, V>> {
+        self.vertices.get(key)
+    }
+
+    /// Get the number of vertices of the graph.
+    /// Complexity: O(1).
+    fn vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+
+    /// Get the number of edges of the graph.
+    /// Complexity: O(1).
+    fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
+    /// Get the number of edges touching a given key, a loop counting twice,
+    /// or [`None`] if the vertex doesn't exist.
+    /// Complexity: O(log V).
+    fn in_degree(&self, key: &K) -> Option<usize> {
+        self.degree(key)
+    }
+
+    /// Get the number of edges touching a given key, a loop counting twice,
+    /// or [`None`] if the vertex doesn't exist.
+    /// Complexity: O(log V).
+    fn out_degree(&self, key: &K) -> Option<usize> {
+        self.degree(key)
+    }
+
+    /// Get the number of edges touching a given key, a loop counting twice,
+    /// or [`None`] if the vertex doesn't exist.
+    /// Complexity: O(log V).
+    fn degree(&self, key: &K) -> Option<usize> {
+        self.adjacency.get(key).map(|incident_edges| {
+            incident_edges.len() + usize::from(incident_edges.contains_key(key))
+        })
+    }
+
+    /// Add a new vertex then return the graph, or fail if the key is already used.
+    /// Complexity: O(log V).
+    fn try_add_vertex(&self, vertex: Vertex<K, V>) -> Result<Self, GraphError<K>> {
         let mut new_graph = self.clone();
-        return if let Some(removed_vertex) = self.vertices.get(&vertex) {
-            if new_graph.vertices.remove(removed_vertex) {
-                if let Some((new_graph, removed_edges)) =
-                    new_graph.internal_remove_all_edges_where_vertex(removed_vertex)
-                {
-                    Some((new_graph, *removed_vertex, removed_edges))
-                } else {
-                    None
-                }
-            } else {
-                None
-            }
-        } else {
-            None
-        };
+        new_graph.insert_vertex(vertex)?;
+
+        Ok(new_graph)
     }
 
     /// Remove all vertices then return the new graph, the deleted vertices and all the edges.
     /// Complexity: O(1*).
-    fn remove_all_vertices(&self) -> Option<(Self, Vec<Vertex<K, V>>, Vec<Edge<K>>)> {
-        let new_graph = BasicUndirectedGraph::new();
+    fn remove_all_vertices(&self) -> Option<(Self, Vec<Vertex<K, V>>, Vec<Edge<K, W>>)> {
+        let new_graph = Self::default();
         let vertices = self.vertices();
         let edges = self.edges();
 
         Some((new_graph, vertices, edges))
     }
 
-    /// Remove a vertex by its key then return the new graph, the deleted vertex and its edges.
-    /// Complexity: O(E).
-    fn remove_vertex_where_key(&self, key: K) -> Option<(Self, Vertex<K, V>, Vec<Edge<K>>)> {
-        let vertex: Vertex<K, V> = Vertex::new(key);
-        self.remove_vertex(&vertex)
+    /// Remove a vertex by its key then return the new graph, the deleted vertex and its edges,
+    /// or fail if the vertex doesn't exist.
+    /// Complexity: O(degree).
+    fn try_remove_vertex_where_key(
+        &self,
+        key: K,
+    ) -> Result<(Self, Vertex<K, V>, Vec<Edge<K, W>>), GraphError<K>> {
+        let mut new_graph = self.clone();
+        let (removed_vertex, removed_edges) = new_graph.remove_vertex_where_key_mut(key)?;
+
+        Ok((new_graph, removed_vertex, removed_edges))
     }
 
-    /// Add a new edge then return the new graph.
-    /// Complexity: O(1*).
-    fn add_edge(&self, edge: Edge<K>) -> Option<Self> {
-        let vertex_from: Vertex<K, V> = Vertex::new(edge.from().clone());
-        let vertex_to: Vertex<K, V> = Vertex::new(edge.to().clone());
-        if !self.vertices.contains(&vertex_from) || !self.vertices.contains(&vertex_to) {
-            return None;
-        }
+    /// Replace the value of the vertex of a given key then return the new graph and the old value,
+    /// or fail if the vertex doesn't exist.
+    /// Complexity: O(log V).
+    fn try_update_vertex_value(&self, key: K, value: V) -> Result<(Self, V), GraphError<K>> {
+        let mut new_graph = self.clone();
+        let old_value = new_graph.update_vertex_value_mut(key, value)?;
 
-        let other_edge = Edge::new(edge.to().clone(), edge.from().clone());
-        if self.edges.contains(&other_edge) {
-            return None;
-        }
+        Ok((new_graph, old_value))
+    }
 
+    /// Compute a new value for every vertex from its key and its value, then return the new graph.
+    /// Complexity: O(V).
+    fn map_vertex_values<F>(&self, f: F) -> Self
+    where
+        F: FnMut(&K, &V) -> V,
+    {
         let mut new_graph = self.clone();
-        return if new_graph.edges.insert(edge) {
-            Some(new_graph)
-        } else {
-            None
-        };
-    }
+        new_graph.map_vertex_values_mut(f);
 
-    /// Add a new edge between 2 keys then return the new graph.
-    /// Complexity: O(1*).
-    fn add_edge_between_keys(&self, key_from: K, key_to: K) -> Option<Self> {
-        self.add_edge(Edge::new(key_from, key_to))
+        new_graph
     }
 
-    /// Remove an existing edge then return the new graph and the deleted edge.
-    /// Complexity: O(1*).
-    fn remove_edge(&self, edge: &Edge<K>) -> Option<(Self, Edge<K>)> {
+    /// Add a new edge then return the new graph,
+    /// or fail if an endpoint is missing or if the edge already exists.
+    /// Complexity: O(log E).
+    fn try_add_edge(&self, edge: Edge<K, W>) -> Result<Self, GraphError<K>> {
         let mut new_graph = self.clone();
+        new_graph.insert_edge(edge)?;
 
-        let other_edge = Edge::new(edge.to().clone(), edge.from().clone());
-        let mut edge_to_remove = edge;
-        if self.edges.contains(&other_edge) {
-            edge_to_remove = &other_edge;
-        }
+        Ok(new_graph)
+    }
 
-        return if let Some(removed_edge) = self.edges.get(edge_to_remove) {
-            if new_graph.edges.remove(removed_edge) {
-                Some((new_graph, *removed_edge))
-            } else {
-                None
-            }
-        } else {
-            None
-        };
+    /// Get the weight of the edge between 2 keys.
+    /// Complexity: O(log E).
+    fn edge_weight(&self, key_from: &K, key_to: &K) -> Option<&W> {
+        self.adjacency
+            .get(key_from)?
+            .get(key_to)
+            .map(|edge| edge.weight())
     }
 
-    /// Remove an existing edge by their keys, then return the new graph and the deleted edge.
-    /// Complexity: O(1*).
-    fn remove_edge_where_keys(&self, key_from: K, key_to: K) -> Option<(Self, Edge<K>)> {
-        let edge = Edge::new(key_from, key_to);
-        self.remove_edge(&edge)
+    /// Replace the weight of the edge between 2 keys then return the new graph and the old weight,
+    /// or fail if the edge doesn't exist.
+    /// Complexity: O(log E).
+    fn try_update_edge_weight(
+        &self,
+        key_from: K,
+        key_to: K,
+        weight: W,
+    ) -> Result<(Self, W), GraphError<K>> {
+        let mut new_graph = self.clone();
+        let old_weight = new_graph.update_edge_weight_mut(key_from, key_to, weight)?;
+
+        Ok((new_graph, old_weight))
+    }
+
+    /// Remove an existing edge by their keys, then return the new graph and the deleted edge,
+    /// or fail if the edge doesn't exist.
+    /// Complexity: O(log E).
+    fn try_remove_edge_where_keys(
+        &self,
+        key_from: K,
+        key_to: K,
+    ) -> Result<(Self, Edge<K, W>), GraphError<K>> {
+        let mut new_graph = self.clone();
+        let removed_edge = new_graph.remove_edge_where_keys_mut(key_from, key_to)?;
+
+        Ok((new_graph, removed_edge))
     }
 
     /// Remove all the edges then return the new graph and all the deleted edges.
-    /// Complexity: O(1*).
-    fn remove_all_edges(&self) -> Option<(Self, Vec<Edge<K>>)> {
-        let new_graph = BasicUndirectedGraph {
-            vertices: self.vertices.clone(),
-            edges: HashSet::new(),
-        };
-        let edges = self.edges();
+    /// Complexity: O(V + E).
+    fn remove_all_edges(&self) -> Option<(Self, Vec<Edge<K, W>>)> {
+        let mut new_graph = self.clone();
+        let edges = new_graph.remove_all_edges_mut();
 
         Some((new_graph, edges))
     }
 
-    /// Remove all existing edges from or to a given vertex, then return the new graph and the deleted edges.
-    /// Complexity: O(E).
-    fn remove_all_edges_where_vertex(&self, vertex: &Vertex<K, V>) -> Option<(Self, Vec<Edge<K>>)> {
-        if !self.vertices.contains(vertex) {
-            return None;
-        }
-        self.internal_remove_all_edges_where_vertex(vertex)
-    }
+    /// Remove all existing edges from or to a given key, then return the new graph and the deleted edges,
+    /// or fail if the vertex doesn't exist.
+    /// Complexity: O(degree).
+    fn try_remove_all_edges_where_key(
+        &self,
+        key_from: K,
+    ) -> Result<(Self, Vec<Edge<K, W>>), GraphError<K>> {
+        let mut new_graph = self.clone();
+        let removed_edges = new_graph.remove_all_edges_where_key_mut(key_from)?;
 
-    /// Remove all existing edges from or to a given key, then return the new graph and the deleted edges.
-    /// Complexity: O(E).
-    fn remove_all_edges_where_key(&self, key_from: K) -> Option<(Self, Vec<Edge<K>>)> {
-        let vertex = Vertex::new(key_from);
-        self.remove_all_edges_where_vertex(&vertex)
+        Ok((new_graph, removed_edges))
     }
 
-    /// Remove all existing edges from a given vertex, then return the new graph and the deleted edges.
-    /// Complexity: O(E).
-    fn remove_all_edges_from_vertex(&self, vertex: &Vertex<K, V>) -> Option<(Self, Vec<Edge<K>>)> {
-        self.remove_all_edges_where_vertex(vertex)
-    }
+    /// Remove all existing edges from a given key, then return the new graph and the deleted edges,
+    /// or fail if the vertex doesn't exist.
+    /// Complexity: O(degree).
+    fn try_remove_all_edges_from_key(
+        &self,
+        key_from: K,
+    ) -> Result<(Self, Vec<Edge<K, W>>), GraphError<K>> {
+        let mut new_graph = self.clone();
+        let removed_edges = new_graph.remove_all_edges_from_key_mut(key_from)?;
 
-    /// Remove all existing edges from a given key, then return the new graph and the deleted edges.
-    /// Complexity: O(E).
-    fn remove_all_edges_from_key(&self, key_from: K) -> Option<(Self, Vec<Edge<K>>)> {
-        let vertex = Vertex::new(key_from);
-        self.remove_all_edges_from_vertex(&vertex)
+        Ok((new_graph, removed_edges))
     }
 }
 
-impl<K, V> Kinship<K, V> for BasicUndirectedGraph<K, V>
+impl<K, V, W, S> AnyGraphMut<K, V, W> for BasicUndirectedGraph<K, V, W, S>
 where
     K: Key,
     V: Value,
+    W: Weight,
+    S: BuildHasher + Default,
 {
-    /// Get the successors of each vertex.
+    /// Insert a new vertex, or fail if the key is already used.
+    /// Complexity: O(log V).
+    fn insert_vertex(&mut self, vertex: Vertex<K, V>) -> Result<(), GraphError<K>> {
+        if self.vertices.contains_key(vertex.key()) {
+            return Err(GraphError::DuplicateVertex(vertex.key().clone()));
+        }
+        self.adjacency
+            .insert(vertex.key().clone(), im::HashMap::default());
+        self.vertices.insert(vertex.key().clone(), vertex);
+        Ok(())
+    }
+
+    /// Remove all vertices then return the deleted vertices and all the edges.
     /// Complexity: O(V + E).
-    fn successors(&self) -> HashMap<Vertex<K, V>, Vec<Edge<K>>, RandomState> {
-        let init_hashmap = self
+    fn remove_all_vertices_mut(&mut self) -> (Vec<Vertex<K, V>>, Vec<Edge<K, W>>) {
+        let edges = self.remove_all_edges_mut();
+        let vertices = std::mem::take(&mut self.vertices)
+            .into_iter()
+            .map(|(_, vertex)| vertex)
+            .collect();
+        self.adjacency = im::HashMap::default();
+
+        (vertices, edges)
+    }
+
+    /// Remove a vertex by its key then return the deleted vertex and its edges.
+    /// Complexity: O(degree).
+    fn remove_vertex_where_key_mut(
+        &mut self,
+        key: K,
+    ) -> Result<(Vertex<K, V>, Vec<Edge<K, W>>), GraphError<K>> {
+        let removed_edges = self.remove_all_edges_where_key_mut(key.clone())?;
+        let removed_vertex = self.vertices.remove(&key).unwrap();
+        self.adjacency.remove(&key);
+
+        Ok((removed_vertex, removed_edges))
+    }
+
+    /// Replace the value of the vertex of a given key then return the old value.
+    /// Complexity: O(log V).
+    fn update_vertex_value_mut(&mut self, key: K, value: V) -> Result<V, GraphError<K>> {
+        let vertex = self
             .vertices
-            .iter()
-            .fold(HashMap::new(), |mut acc, vertex| {
-                let vec: Vec<Edge<K>> = vec![];
-                acc.insert(vertex.clone(), vec);
-                acc
-            });
-        self.edges.iter().fold(init_hashmap, |mut acc, edge| {
-            let tmp_from_vertex = Vertex::new(edge.from().clone());
-            let tmp_to_vertex = Vertex::new(edge.to().clone());
-            if let Some(vector) = acc.get_mut(&tmp_from_vertex) {
-                vector.push(edge.clone());
-            }
-            if edge.from().ne(edge.to()) {
-                if let Some(vector) = acc.get_mut(&tmp_to_vertex) {
-                    vector.push(edge.clone());
-                }
-            }
-            acc
-        })
+            .get_mut(&key)
+            .ok_or_else(|| GraphError::VertexNotFound(key.clone()))?;
+        let old_value = vertex.value().clone();
+        vertex.set_value(value);
+
+        Ok(old_value)
+    }
+
+    /// Compute a new value for every vertex from its key and its value.
+    /// Complexity: O(V).
+    fn map_vertex_values_mut<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&K, &V) -> V,
+    {
+        for (key, vertex) in self.vertices.iter_mut() {
+            let value = f(key, vertex.value());
+            vertex.set_value(value);
+        }
+    }
+
+    /// Insert a new edge, or fail if an endpoint is missing or if the edge already exists.
+    /// Complexity: O(log E).
+    fn insert_edge(&mut self, edge: Edge<K, W>) -> Result<(), GraphError<K>> {
+        self.check_vertex(edge.from())?;
+        self.check_vertex(edge.to())?;
+        if self.edge_weight(edge.from(), edge.to()).is_some() {
+            return Err(GraphError::DuplicateEdge(Edge::new(
+                edge.from().clone(),
+                edge.to().clone(),
+            )));
+        }
+
+        let edge = UndirectedEdge::from(edge);
+        let (key_a, key_b) = edge.endpoints();
+        let (key_a, key_b) = (key_a.clone(), key_b.clone());
+        self.adjacency[&key_a].insert(key_b.clone(), edge.clone());
+        self.adjacency[&key_b].insert(key_a, edge);
+        self.edge_count += 1;
+        Ok(())
+    }
+
+    /// Replace the weight of the edge between 2 keys then return the old weight.
+    /// Complexity: O(log E).
+    fn update_edge_weight_mut(
+        &mut self,
+        key_from: K,
+        key_to: K,
+        weight: W,
+    ) -> Result<W, GraphError<K>> {
+        let old_weight = self
+            .edge_weight(&key_from, &key_to)
+            .cloned()
+            .ok_or_else(|| GraphError::EdgeNotFound(Edge::new(key_from.clone(), key_to.clone())))?;
+
+        self.adjacency[&key_from][&key_to].set_weight(weight.clone());
+        self.adjacency[&key_to][&key_from].set_weight(weight);
+        Ok(old_weight)
     }
 
-    /// Get the predecessors of each vertex.
+    /// Remove an existing edge by their keys, then return the deleted edge.
+    /// Complexity: O(log E).
+    fn remove_edge_where_keys_mut(
+        &mut self,
+        key_from: K,
+        key_to: K,
+    ) -> Result<Edge<K, W>, GraphError<K>> {
+        self.unlink(&key_from, &key_to)
+            .ok_or_else(|| GraphError::EdgeNotFound(Edge::new(key_from, key_to)))
+    }
+
+    /// Remove all the edges then return all the deleted edges.
     /// Complexity: O(V + E).
-    fn predecessors(&self) -> HashMap<Vertex<K, V>, Vec<Edge<K>>, RandomState> {
-        self.successors()
+    fn remove_all_edges_mut(&mut self) -> Vec<Edge<K, W>> {
+        let removed_edges = self.edges();
+        self.adjacency = self
+            .vertices
+            .keys()
+            .map(|key| (key.clone(), im::HashMap::default()))
+            .collect();
+        self.edge_count = 0;
+
+        removed_edges
+    }
+
+    /// Remove all existing edges from or to a given key, then return the deleted edges.
+    /// Complexity: O(degree).
+    fn remove_all_edges_where_key_mut(
+        &mut self,
+        key_from: K,
+    ) -> Result<Vec<Edge<K, W>>, GraphError<K>> {
+        self.check_vertex(&key_from)?;
+        let other_keys: Vec<K> = self.adjacency[&key_from].keys().cloned().collect();
+
+        Ok(other_keys
+            .iter()
+            .filter_map(|other_key| self.unlink(&key_from, other_key))
+            .collect())
+    }
+
+    /// Remove all existing edges from a given key, then return the deleted edges.
+    /// Complexity: O(degree).
+    fn remove_all_edges_from_key_mut(
+        &mut self,
+        key_from: K,
+    ) -> Result<Vec<Edge<K, W>>, GraphError<K>> {
+        self.remove_all_edges_where_key_mut(key_from)
+    }
+}
+
+impl<K, V, W, S> Kinship<K, V, W, S> for BasicUndirectedGraph<K, V, W, S>
+where
+    K: Key,
+    V: Value,
+    W: Weight,
+    S: BuildHasher + Default,
+{
+    /// Iterate over the keys of the vertices at the other end of the edges touching a given key.
+    /// Complexity: O(log V).
+    fn neighbors_iter<'a>(&'a self, key: &K) -> Option<impl ExactSizeIterator<Item = &'a K>>
+    where
+        K: 'a,
+    {
+        self.adjacency
+            .get(key)
+            .map(|incident_edges| incident_edges.keys())
+    }
+
+    /// Iterate over the edges touching a given key, each going from its smallest key to the other one.
+    /// Complexity: O(log V).
+    fn out_edges_iter<'a>(
+        &'a self,
+        key: &K,
+    ) -> Option<impl ExactSizeIterator<Item = &'a Edge<K, W>>>
+    where
+        K: 'a,
+        W: 'a,
+    {
+        self.adjacency
+            .get(key)
+            .map(|incident_edges| incident_edges.values().map(|edge| edge.as_edge()))
+    }
+
+    /// Iterate over the edges touching a given key, each going from its smallest key to the other one.
+    /// Complexity: O(log V).
+    fn in_edges_iter<'a>(&'a self, key: &K) -> Option<impl ExactSizeIterator<Item = &'a Edge<K, W>>>
+    where
+        K: 'a,
+        W: 'a,
+    {
+        self.out_edges_iter(key)
+    }
+}
+
+impl<K, V, W, S> Algorithms<K, V, W, S> for BasicUndirectedGraph<K, V, W, S>
+where
+    K: Key,
+    V: Value,
+    W: Weight,
+    S: BuildHasher + Default,
+{
+}
+
+impl<K, V, W, S> Clone for BasicUndirectedGraph<K, V, W, S>
+where
+    K: Key,
+    V: Value,
+    W: Weight,
+    S: BuildHasher,
+{
+    fn clone(&self) -> Self {
+        BasicUndirectedGraph {
+            vertices: self.vertices.clone(),
+            adjacency: self.adjacency.clone(),
+            edge_count: self.edge_count,
+        }
     }
 }
 
-impl<K, V> Algorithms<K, V> for BasicUndirectedGraph<K, V>
+impl<K, V, W, S> PartialEq for BasicUndirectedGraph<K, V, W, S>
 where
     K: Key,
     V: Value,
+    W: Weight,
+    S: BuildHasher,
 {
+    fn eq(&self, other: &Self) -> bool {
+        self.vertices == other.vertices
+            && self.adjacency == other.adjacency
+            && self.edge_count == other.edge_count
+            && same_weights(
+                self.adjacency
+                    .values()
+                    .flat_map(|edges| edges.values())
+                    .map(|edge| {
+                        let (key_a, key_b) = edge.endpoints();
+                        let counterpart = other
+                            .adjacency
+                            .get(key_a)
+                            .and_then(|edges| edges.get(key_b));
+                        (edge.as_edge(), counterpart.map(UndirectedEdge::as_edge))
+                    }),
+            )
+    }
 }
 
-impl<K, V> BasicUndirectedGraph<K, V>
+impl<K, V, W, S> Default for BasicUndirectedGraph<K, V, W, S>
 where
     K: Key,
     V: Value,
+    W: Weight,
+    S: BuildHasher + Default,
+{
+    fn default() -> Self {
+        BasicUndirectedGraph {
+            vertices: im::HashMap::default(),
+            adjacency: im::HashMap::default(),
+            edge_count: 0,
+        }
+    }
+}
+
+impl<K, V, W> BasicUndirectedGraph<K, V, W>
+where
+    K: Key,
+    V: Value,
+    W: Weight,
 {
     /// Create a new undirected graph.
     /// Complexity: O(1)
     pub fn new() -> Self {
-        BasicUndirectedGraph {
-            vertices: HashSet::new(),
-            edges: HashSet::new(),
-        }
+        Self::default()
+    }
+}
+
+impl<K, V, W, S> BasicUndirectedGraph<K, V, W, S>
+where
+    K: Key,
+    V: Value,
+    W: Weight,
+    S: BuildHasher + Default,
+{
+    /// Iterate over the edges of the graph as [`UndirectedEdge`].
+    /// Complexity: O(1).
+    pub fn undirected_edges_iter(&self) -> impl ExactSizeIterator<Item = &UndirectedEdge<K, W>> {
+        let edges = self.adjacency.iter().flat_map(|(key, incident_edges)| {
+            incident_edges
+                .iter()
+                .filter(move |(other_key, _)| key <= *other_key)
+                .map(|(_, edge)| edge)
+        });
+        Counted::new(edges, self.edge_count)
     }
 
-    fn internal_remove_all_edges_where_vertex(
+    /// Get the edges of the graph as [`UndirectedEdge`].
+    /// Complexity: O(E).
+    pub fn undirected_edges(&self) -> Vec<UndirectedEdge<K, W>> {
+        self.undirected_edges_iter().cloned().collect()
+    }
+
+    /// Iterate over the edges touching a given key as [`UndirectedEdge`],
+    /// or [`None`] if the vertex doesn't exist.
+    /// Complexity: O(log V).
+    pub fn incident_edges_iter(
         &self,
-        vertex: &Vertex<K, V>,
-    ) -> Option<(Self, Vec<Edge<K>>)> {
-        let mut new_graph = self.clone();
-        let removed_edges: Vec<Edge<K>> = new_graph
-            .edges
-            .iter()
-            .cloned()
-            .filter(|edge| edge.from().eq(vertex.key()) || edge.to().eq(vertex.key()))
-            .collect();
-        new_graph.edges = new_graph
-            .edges
-            .into_iter()
-            .filter(|edge| !(edge.from().eq(vertex.key()) || edge.to().eq(vertex.key())))
-            .collect();
+        key: &K,
+    ) -> Option<impl ExactSizeIterator<Item = &UndirectedEdge<K, W>>> {
+        self.adjacency
+            .get(key)
+            .map(|incident_edges| incident_edges.values())
+    }
+}
+
+impl<K, V, W, S> BasicUndirectedGraph<K, V, W, S>
+where
+    K: Key,
+    V: Value,
+    W: Weight,
+    S: BuildHasher + Default,
+{
+    fn check_vertex(&self, key: &K) -> Result<(), GraphError<K>> {
+        if self.vertices.contains_key(key) {
+            Ok(())
+        } else {
+            Err(GraphError::VertexNotFound(key.clone()))
+        }
+    }
+
+    fn unlink(&mut self, key_from: &K, key_to: &K) -> Option<Edge<K, W>> {
+        let removed_edge = self.adjacency.get_mut(key_from)?.remove(key_to)?;
+        self.adjacency[key_to].remove(key_from);
+        self.edge_count -= 1;
 
-        Some((new_graph, removed_edges))
+        Some(removed_edge.into())
     }
 }
@@ -0,0 +1,191 @@
+use crate::visit::dfs_visit_from;
+use crate::{Algorithms, Control, Cycle, DfsEvent, Key, Value, Weight};
+use std::cmp::Reverse;
+use std::collections::hash_map::RandomState;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::hash::BuildHasher;
+
+/// An interface describing the algorithms that only make sense on directed graphs.
+pub trait DirectedAlgorithms<K, V, W = (), S = RandomState>: Algorithms<K, V, W, S>
+where
+    K: Key,
+    V: Value,
+    W: Weight,
+    S: BuildHasher + Default,
+{
+    /// Sort the keys so that every edge goes from a key to a later one,
+    /// or fail with one of the cycles preventing it.
+    /// There is no order between the keys not tied by a path, see
+    /// [`DirectedAlgorithms::lexicographic_topological_sort`] for a stable one.
+    /// Complexity: O(V log V + E).
+    fn topological_sort(&self) -> Result<Vec<K>, Cycle<K>> {
+        let mut keys: Vec<&K> = self.keys().collect();
+        keys.sort();
+        let mut discovered: HashSet<K, S> = HashSet::default();
+        let mut finished: HashSet<K, S> = HashSet::default();
+        let mut path: Vec<K> = Vec::new();
+        let mut postorder: Vec<K> = Vec::with_capacity(keys.len());
+        let mut cycle = None;
+
+        for root in keys {
+            if discovered.contains(root) {
+                continue;
+            }
+            let control =
+                dfs_visit_from(self, root, &mut discovered, &mut finished, &mut |event| {
+                    match event {
+                        DfsEvent::Discover(key) => path.push(key.clone()),
+                        // the finished key is the last one of the path
+                        DfsEvent::Finish(_) => postorder.push(path.pop().unwrap()),
+                        // the path ends at the key the edge comes from and goes through its ancestor
+                        DfsEvent::BackEdge(_, ancestor) => {
+                            let start = path.iter().position(|key| key == ancestor).unwrap();
+                            cycle = Some(Cycle::new(path.split_off(start)));
+                            return Control::Break;
+                        }
+                        _ => {}
+                    }
+                    Control::Continue
+                });
+            if control == Control::Break {
+                break;
+            }
+        }
+
+        match cycle {
+            Some(cycle) => Err(cycle),
+            None => {
+                postorder.reverse();
+                Ok(postorder)
+            }
+        }
+    }
+
+    /// Sort the keys so that every edge goes from a key to a later one, taking the smallest key
+    /// available each time, or fail with one of the cycles preventing it.
+    /// Complexity: O((V + E) log V).
+    fn lexicographic_topological_sort(&self) -> Result<Vec<K>, Cycle<K>> {
+        let mut in_degree: HashMap<K, usize, S> = HashMap::default();
+        let mut available = BinaryHeap::new();
+        let mut order = Vec::with_capacity(self.vertex_count());
+
+        for key in self.keys() {
+            let degree = self.in_degree(key).unwrap();
+            if degree == 0 {
+                available.push(Reverse(key.clone()));
+            } else {
+                in_degree.insert(key.clone(), degree);
+            }
+        }
+        while let Some(Reverse(key)) = available.pop() {
+            for edge in self.out_edges_iter(&key).unwrap() {
+                let degree = in_degree.get_mut(edge.to()).unwrap();
+                *degree -= 1;
+                if *degree == 0 {
+                    in_degree.remove(edge.to());
+                    available.push(Reverse(edge.to().clone()));
+                }
+            }
+            order.push(key);
+        }
+
+        if in_degree.is_empty() {
+            Ok(order)
+        } else {
+            Err(remaining_cycle(self, &in_degree))
+        }
+    }
+
+    /// Check if the graph has no cycle, loops included.
+    /// Complexity: O(V log V + E).
+    fn is_acyclic(&self) -> bool {
+        self.topological_sort().is_ok()
+    }
+
+    /// Split the keys into generations, each key only having predecessors in earlier generations,
+    /// so that the keys of a generation don't depend on each other,
+    /// or fail with one of the cycles preventing it.
+    /// Each generation is sorted by increasing key.
+    /// Complexity: O((V + E) log V).
+    fn topological_generations(&self) -> Result<Vec<Vec<K>>, Cycle<K>> {
+        let mut in_degree: HashMap<K, usize, S> = HashMap::default();
+        let mut generation = Vec::new();
+        let mut generations = Vec::new();
+
+        for (key, edges) in self.predecessors_as_key_and_edges() {
+            if edges.is_empty() {
+                generation.push(key);
+            } else {
+                in_degree.insert(key, edges.len());
+            }
+        }
+        while !generation.is_empty() {
+            generation.sort();
+            let mut next_generation = Vec::new();
+            for key in &generation {
+                for edge in self.out_edges_iter(key).unwrap() {
+                    let degree = in_degree.get_mut(edge.to()).unwrap();
+                    *degree -= 1;
+                    if *degree == 0 {
+                        in_degree.remove(edge.to());
+                        next_generation.push(edge.to().clone());
+                    }
+                }
+            }
+            generations.push(generation);
+            generation = next_generation;
+        }
+
+        if in_degree.is_empty() {
+            Ok(generations)
+        } else {
+            Err(remaining_cycle(self, &in_degree))
+        }
+    }
+
+    /// Get the number of edges of the longest path leading to each key, which is the index of
+    /// its generation, or fail with one of the cycles making it endless.
+    /// See [`DirectedAlgorithms::topological_generations`].
+    /// Complexity: O((V + E) log V).
+    fn longest_path_depths(&self) -> Result<HashMap<K, usize, S>, Cycle<K>> {
+        let generations = self.topological_generations()?;
+
+        Ok(generations
+            .into_iter()
+            .enumerate()
+            .flat_map(|(depth, generation)| generation.into_iter().map(move |key| (key, depth)))
+            .collect())
+    }
+}
+
+/// Get a cycle among the keys left by Kahn's algorithm, which all keep a predecessor left.
+/// Going back from predecessor to predecessor is sure to loop.
+fn remaining_cycle<G, K, V, W, S>(graph: &G, remaining: &HashMap<K, usize, S>) -> Cycle<K>
+where
+    G: DirectedAlgorithms<K, V, W, S>,
+    K: Key,
+    V: Value,
+    W: Weight,
+    S: BuildHasher + Default,
+{
+    let mut walk: Vec<K> = Vec::new();
+    let mut position: HashMap<K, usize, S> = HashMap::default();
+    let mut current = remaining.keys().min().unwrap().clone();
+
+    while !position.contains_key(&current) {
+        position.insert(current.clone(), walk.len());
+        walk.push(current.clone());
+        current = graph
+            .in_edges_iter(&current)
+            .unwrap()
+            .map(|edge| edge.from())
+            .filter(|key| remaining.contains_key(key))
+            .min()
+            .unwrap()
+            .clone();
+    }
+    let mut keys = walk.split_off(position[&current]);
+    keys.reverse();
+
+    Cycle::new(keys)
+}
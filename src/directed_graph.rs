@@ -0,0 +1,578 @@
+use crate::algo::Algorithms;
+use crate::any_graph::AnyGraph;
+use crate::any_graph_mut::AnyGraphMut;
+use crate::directed_algo::DirectedAlgorithms;
+use crate::iter::Counted;
+use crate::kinship::Kinship;
+use crate::storage::{Storage, StorageMap};
+use crate::types::{same_weights, Key, Value, Vertex, Weight};
+use crate::{Edge, GraphError};
+
+/// A directed graph storing its vertices and edges in the persistent maps of `M`.
+/// It doesn't allow multiple edges but allow loops.
+/// Every new graph returned by [`AnyGraph`] shares most of its structure with the graph it was
+/// created from.
+/// Each vertex indexes its outgoing and incoming edges, so the edges of a vertex are found
+/// in O(degree).
+/// It is used through [`BasicDirectedGraph`](crate::BasicDirectedGraph), storing them in hash
+/// maps, and [`OrderedDirectedGraph`](crate::OrderedDirectedGraph), storing them in ordered maps.
+pub struct DirectedGraph<K, V, W, M>
+where
+    K: Key,
+    V: Value,
+    W: Weight,
+    M: Storage,
+{
+    vertices: M::Map<K, Vertex<K, V>>,
+    outgoing: M::Map<K, M::Map<K, Edge<K, W>>>,
+    incoming: M::Map<K, M::Map<K, Edge<K, W>>>,
+    edge_count: usize,
+}
+
+impl<K, V, W, M> AnyGraph<K, V, W> for DirectedGraph<K, V, W, M>
+where
+    K: Key,
+    V: Value,
+    W: Weight,
+    M: Storage,
+{
+    /// Iterate over the vertices of the graph.
+    /// Complexity: O(1).
+    fn vertices_iter<'a>(&'a self) -> impl ExactSizeIterator<Item = &'a Vertex<K, V>>
+    where
+        K: 'a,
+        V: 'a,
+    {
+        self.vertices.values()
+    }
+
+    /// Iterate over the keys of the vertices of the graph.
+    /// Complexity: O(1).
+    fn keys<'a>(&'a self) -> impl ExactSizeIterator<Item = &'a K>
+    where
+        K: 'a,
+    {
+        self.vertices.keys()
+    }
+
+    /// Iterate over the edges of the graph.
+    /// Complexity: O(1).
+    fn edges_iter<'a>(&'a self) -> impl ExactSizeIterator<Item = &'a Edge<K, W>>
+    where
+        K: 'a,
+        W: 'a,
+    {
+        Counted::new(
+            self.outgoing.values().flat_map(|targets| targets.values()),
+            self.edge_count,
+        )
+    }
+
+    /// Get the vertex of a given key.
+    /// Complexity: O(log V).
+    fn get_vertex(&self, key: &K) -> Option<&Vertex<K, V>> {
+        self.vertices.get(key)
+    }
+
+    /// Get the number of vertices of the graph.
+    /// Complexity: O(1).
+    fn vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+
+    /// Get the number of edges of the graph.
+    /// Complexity: O(1).
+    fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
+    /// Get the number of edges to a given key, or [`None`] if the vertex doesn't exist.
+    /// Complexity: O(log V).
+    fn in_degree(&self, key: &K) -> Option<usize> {
+        self.incoming.get(key).map(|sources| sources.len())
+    }
+
+    /// Get the number of edges from a given key, or [`None`] if the vertex doesn't exist.
+    /// Complexity: O(log V).
+    fn out_degree(&self, key: &K) -> Option<usize> {
+        self.outgoing.get(key).map(|targets| targets.len())
+    }
+
+    /// Add a new vertex then return the graph, or fail if the key is already used.
+    /// Complexity: O(log V).
+    fn try_add_vertex(&self, vertex: Vertex<K, V>) -> Result<Self, GraphError<K>> {
+        let mut new_graph = self.clone();
+        new_graph.insert_vertex(vertex)?;
+
+        Ok(new_graph)
+    }
+
+    /// Remove all vertices then return the new graph, the deleted vertices and all the edges.
+    /// Complexity: O(1*).
+    fn remove_all_vertices(&self) -> Option<(Self, Vec<Vertex<K, V>>, Vec<Edge<K, W>>)> {
+        let new_graph = Self::default();
+        let vertices = self.vertices();
+        let edges = self.edges();
+
+        Some((new_graph, vertices, edges))
+    }
+
+    /// Remove a vertex by its key then return the new graph, the deleted vertex and its edges,
+    /// or fail if the vertex doesn't exist.
+    /// Complexity: O(degree).
+    fn try_remove_vertex_where_key(
+        &self,
+        key: K,
+    ) -> Result<(Self, Vertex<K, V>, Vec<Edge<K, W>>), GraphError<K>> {
+        let mut new_graph = self.clone();
+        let (removed_vertex, removed_edges) = new_graph.remove_vertex_where_key_mut(key)?;
+
+        Ok((new_graph, removed_vertex, removed_edges))
+    }
+
+    /// Replace the value of the vertex of a given key then return the new graph and the old value,
+    /// or fail if the vertex doesn't exist.
+    /// Complexity: O(log V).
+    fn try_update_vertex_value(&self, key: K, value: V) -> Result<(Self, V), GraphError<K>> {
+        let mut new_graph = self.clone();
+        let old_value = new_graph.update_vertex_value_mut(key, value)?;
+
+        Ok((new_graph, old_value))
+    }
+
+    /// Compute a new value for every vertex from its key and its value, then return the new graph.
+    /// Complexity: O(V), O(V log V) in ordered maps.
+    fn map_vertex_values<F>(&self, f: F) -> Self
+    where
+        F: FnMut(&K, &V) -> V,
+    {
+        let mut new_graph = self.clone();
+        new_graph.map_vertex_values_mut(f);
+
+        new_graph
+    }
+
+    /// Add a new edge then return the new graph,
+    /// or fail if an endpoint is missing or if the edge already exists.
+    /// Complexity: O(log E).
+    fn try_add_edge(&self, edge: Edge<K, W>) -> Result<Self, GraphError<K>> {
+        let mut new_graph = self.clone();
+        new_graph.insert_edge(edge)?;
+
+        Ok(new_graph)
+    }
+
+    /// Get the weight of the edge between 2 keys.
+    /// Complexity: O(log E).
+    fn edge_weight(&self, key_from: &K, key_to: &K) -> Option<&W> {
+        self.outgoing
+            .get(key_from)?
+            .get(key_to)
+            .map(|edge| edge.weight())
+    }
+
+    /// Replace the weight of the edge between 2 keys then return the new graph and the old weight,
+    /// or fail if the edge doesn't exist.
+    /// Complexity: O(log E).
+    fn try_update_edge_weight(
+        &self,
+        key_from: K,
+        key_to: K,
+        weight: W,
+    ) -> Result<(Self, W), GraphError<K>> {
+        let mut new_graph = self.clone();
+        let old_weight = new_graph.update_edge_weight_mut(key_from, key_to, weight)?;
+
+        Ok((new_graph, old_weight))
+    }
+
+    /// Remove an existing edge by their keys, then return the new graph and the deleted edge,
+    /// or fail if the edge doesn't exist.
+    /// Complexity: O(log E).
+    fn try_remove_edge_where_keys(
+        &self,
+        key_from: K,
+        key_to: K,
+    ) -> Result<(Self, Edge<K, W>), GraphError<K>> {
+        let mut new_graph = self.clone();
+        let removed_edge = new_graph.remove_edge_where_keys_mut(key_from, key_to)?;
+
+        Ok((new_graph, removed_edge))
+    }
+
+    /// Remove all the edges then return the new graph and all the deleted edges.
+    /// Complexity: O(V + E).
+    fn remove_all_edges(&self) -> Option<(Self, Vec<Edge<K, W>>)> {
+        let mut new_graph = self.clone();
+        let edges = new_graph.remove_all_edges_mut();
+
+        Some((new_graph, edges))
+    }
+
+    /// Remove all existing edges from or to a given key, then return the new graph and the deleted edges,
+    /// or fail if the vertex doesn't exist.
+    /// Complexity: O(degree).
+    fn try_remove_all_edges_where_key(
+        &self,
+        key_from: K,
+    ) -> Result<(Self, Vec<Edge<K, W>>), GraphError<K>> {
+        let mut new_graph = self.clone();
+        let removed_edges = new_graph.remove_all_edges_where_key_mut(key_from)?;
+
+        Ok((new_graph, removed_edges))
+    }
+
+    /// Remove all existing edges from a given key, then return the new graph and the deleted edges,
+    /// or fail if the vertex doesn't exist.
+    /// Complexity: O(out-degree).
+    fn try_remove_all_edges_from_key(
+        &self,
+        key_from: K,
+    ) -> Result<(Self, Vec<Edge<K, W>>), GraphError<K>> {
+        let mut new_graph = self.clone();
+        let removed_edges = new_graph.remove_all_edges_from_key_mut(key_from)?;
+
+        Ok((new_graph, removed_edges))
+    }
+}
+
+impl<K, V, W, M> AnyGraphMut<K, V, W> for DirectedGraph<K, V, W, M>
+where
+    K: Key,
+    V: Value,
+    W: Weight,
+    M: Storage,
+{
+    /// Insert a new vertex, or fail if the key is already used.
+    /// Complexity: O(log V).
+    fn insert_vertex(&mut self, vertex: Vertex<K, V>) -> Result<(), GraphError<K>> {
+        if self.vertices.contains_key(vertex.key()) {
+            return Err(GraphError::DuplicateVertex(vertex.key().clone()));
+        }
+        self.outgoing
+            .insert(vertex.key().clone(), M::Map::default());
+        self.incoming
+            .insert(vertex.key().clone(), M::Map::default());
+        self.vertices.insert(vertex.key().clone(), vertex);
+        Ok(())
+    }
+
+    /// Remove all vertices then return the deleted vertices and all the edges.
+    /// Complexity: O(V + E).
+    fn remove_all_vertices_mut(&mut self) -> (Vec<Vertex<K, V>>, Vec<Edge<K, W>>) {
+        let edges = self.remove_all_edges_mut();
+        let vertices = std::mem::take(&mut self.vertices)
+            .values()
+            .cloned()
+            .collect();
+        self.outgoing = M::Map::default();
+        self.incoming = M::Map::default();
+
+        (vertices, edges)
+    }
+
+    /// Remove a vertex by its key then return the deleted vertex and its edges.
+    /// Complexity: O(degree).
+    fn remove_vertex_where_key_mut(
+        &mut self,
+        key: K,
+    ) -> Result<(Vertex<K, V>, Vec<Edge<K, W>>), GraphError<K>> {
+        let removed_edges = self.remove_all_edges_where_key_mut(key.clone())?;
+        let removed_vertex = self.vertices.remove(&key).unwrap();
+        self.outgoing.remove(&key);
+        self.incoming.remove(&key);
+
+        Ok((removed_vertex, removed_edges))
+    }
+
+    /// Replace the value of the vertex of a given key then return the old value.
+    /// Complexity: O(log V).
+    fn update_vertex_value_mut(&mut self, key: K, value: V) -> Result<V, GraphError<K>> {
+        let vertex = self
+            .vertices
+            .get_mut(&key)
+            .ok_or_else(|| GraphError::VertexNotFound(key.clone()))?;
+        let old_value = vertex.value().clone();
+        vertex.set_value(value);
+
+        Ok(old_value)
+    }
+
+    /// Compute a new value for every vertex from its key and its value.
+    /// Complexity: O(V), O(V log V) in ordered maps.
+    fn map_vertex_values_mut<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&K, &V) -> V,
+    {
+        self.vertices.update_values(|key, vertex| {
+            let value = f(key, vertex.value());
+            vertex.set_value(value);
+        });
+    }
+
+    /// Insert a new edge, or fail if an endpoint is missing or if the edge already exists.
+    /// Complexity: O(log E).
+    fn insert_edge(&mut self, edge: Edge<K, W>) -> Result<(), GraphError<K>> {
+        self.check_vertex(edge.from())?;
+        self.check_vertex(edge.to())?;
+        if self.edge_weight(edge.from(), edge.to()).is_some() {
+            return Err(GraphError::DuplicateEdge(Edge::new(
+                edge.from().clone(),
+                edge.to().clone(),
+            )));
+        }
+
+        self.outgoing
+            .get_mut(edge.from())
+            .unwrap()
+            .insert(edge.to().clone(), edge.clone());
+        self.incoming
+            .get_mut(edge.to())
+            .unwrap()
+            .insert(edge.from().clone(), edge);
+        self.edge_count += 1;
+        Ok(())
+    }
+
+    /// Replace the weight of the edge between 2 keys then return the old weight.
+    /// Complexity: O(log E).
+    fn update_edge_weight_mut(
+        &mut self,
+        key_from: K,
+        key_to: K,
+        weight: W,
+    ) -> Result<W, GraphError<K>> {
+        let old_weight = self
+            .edge_weight(&key_from, &key_to)
+            .cloned()
+            .ok_or_else(|| GraphError::EdgeNotFound(Edge::new(key_from.clone(), key_to.clone())))?;
+
+        self.outgoing
+            .get_mut(&key_from)
+            .and_then(|targets| targets.get_mut(&key_to))
+            .unwrap()
+            .set_weight(weight.clone());
+        self.incoming
+            .get_mut(&key_to)
+            .and_then(|sources| sources.get_mut(&key_from))
+            .unwrap()
+            .set_weight(weight);
+        Ok(old_weight)
+    }
+
+    /// Remove an existing edge by their keys, then return the deleted edge.
+    /// Complexity: O(log E).
+    fn remove_edge_where_keys_mut(
+        &mut self,
+        key_from: K,
+        key_to: K,
+    ) -> Result<Edge<K, W>, GraphError<K>> {
+        self.unlink(&key_from, &key_to)
+            .ok_or_else(|| GraphError::EdgeNotFound(Edge::new(key_from, key_to)))
+    }
+
+    /// Remove all the edges then return all the deleted edges.
+    /// Complexity: O(V + E).
+    fn remove_all_edges_mut(&mut self) -> Vec<Edge<K, W>> {
+        let removed_edges = self.edges();
+        self.outgoing = self.empty_index();
+        self.incoming = self.empty_index();
+        self.edge_count = 0;
+
+        removed_edges
+    }
+
+    /// Remove all existing edges from or to a given key, then return the deleted edges.
+    /// Complexity: O(degree).
+    fn remove_all_edges_where_key_mut(
+        &mut self,
+        key_from: K,
+    ) -> Result<Vec<Edge<K, W>>, GraphError<K>> {
+        let mut removed_edges = self.remove_all_edges_from_key_mut(key_from.clone())?;
+        let sources: Vec<K> = self
+            .incoming
+            .get(&key_from)
+            .unwrap()
+            .keys()
+            .cloned()
+            .collect();
+        removed_edges.extend(
+            sources
+                .iter()
+                .filter_map(|source| self.unlink(source, &key_from)),
+        );
+
+        Ok(removed_edges)
+    }
+
+    /// Remove all existing edges from a given key, then return the deleted edges.
+    /// Complexity: O(out-degree).
+    fn remove_all_edges_from_key_mut(
+        &mut self,
+        key_from: K,
+    ) -> Result<Vec<Edge<K, W>>, GraphError<K>> {
+        self.check_vertex(&key_from)?;
+        let targets: Vec<K> = self
+            .outgoing
+            .get(&key_from)
+            .unwrap()
+            .keys()
+            .cloned()
+            .collect();
+
+        Ok(targets
+            .iter()
+            .filter_map(|target| self.unlink(&key_from, target))
+            .collect())
+    }
+}
+
+impl<K, V, W, M> Kinship<K, V, W, M::Hasher> for DirectedGraph<K, V, W, M>
+where
+    K: Key,
+    V: Value,
+    W: Weight,
+    M: Storage,
+{
+    /// Iterate over the keys of the vertices at the end of the edges from a given key.
+    /// Complexity: O(log V).
+    fn neighbors_iter<'a>(&'a self, key: &K) -> Option<impl ExactSizeIterator<Item = &'a K>>
+    where
+        K: 'a,
+    {
+        self.outgoing.get(key).map(|targets| targets.keys())
+    }
+
+    /// Iterate over the edges from a given key.
+    /// Complexity: O(log V).
+    fn out_edges_iter<'a>(
+        &'a self,
+        key: &K,
+    ) -> Option<impl ExactSizeIterator<Item = &'a Edge<K, W>>>
+    where
+        K: 'a,
+        W: 'a,
+    {
+        self.outgoing.get(key).map(|targets| targets.values())
+    }
+
+    /// Iterate over the edges to a given key.
+    /// Complexity: O(log V).
+    fn in_edges_iter<'a>(&'a self, key: &K) -> Option<impl ExactSizeIterator<Item = &'a Edge<K, W>>>
+    where
+        K: 'a,
+        W: 'a,
+    {
+        self.incoming.get(key).map(|sources| sources.values())
+    }
+}
+
+impl<K, V, W, M> Algorithms<K, V, W, M::Hasher> for DirectedGraph<K, V, W, M>
+where
+    K: Key,
+    V: Value,
+    W: Weight,
+    M: Storage,
+{
+}
+
+impl<K, V, W, M> DirectedAlgorithms<K, V, W, M::Hasher> for DirectedGraph<K, V, W, M>
+where
+    K: Key,
+    V: Value,
+    W: Weight,
+    M: Storage,
+{
+}
+
+impl<K, V, W, M> Clone for DirectedGraph<K, V, W, M>
+where
+    K: Key,
+    V: Value,
+    W: Weight,
+    M: Storage,
+{
+    fn clone(&self) -> Self {
+        DirectedGraph {
+            vertices: self.vertices.clone(),
+            outgoing: self.outgoing.clone(),
+            incoming: self.incoming.clone(),
+            edge_count: self.edge_count,
+        }
+    }
+}
+
+impl<K, V, W, M> PartialEq for DirectedGraph<K, V, W, M>
+where
+    K: Key,
+    V: Value,
+    W: Weight,
+    M: Storage,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.vertices == other.vertices
+            && self.outgoing == other.outgoing
+            && self.incoming == other.incoming
+            && self.edge_count == other.edge_count
+            && same_weights(
+                self.outgoing
+                    .values()
+                    .flat_map(|edges| edges.values())
+                    .map(|edge| {
+                        let counterpart = other
+                            .outgoing
+                            .get(edge.from())
+                            .and_then(|edges| edges.get(edge.to()));
+                        (edge, counterpart)
+                    }),
+            )
+    }
+}
+
+impl<K, V, W, M> Default for DirectedGraph<K, V, W, M>
+where
+    K: Key,
+    V: Value,
+    W: Weight,
+    M: Storage,
+{
+    fn default() -> Self {
+        DirectedGraph {
+            vertices: M::Map::default(),
+            outgoing: M::Map::default(),
+            incoming: M::Map::default(),
+            edge_count: 0,
+        }
+    }
+}
+
+impl<K, V, W, M> DirectedGraph<K, V, W, M>
+where
+    K: Key,
+    V: Value,
+    W: Weight,
+    M: Storage,
+{
+    fn check_vertex(&self, key: &K) -> Result<(), GraphError<K>> {
+        if self.vertices.contains_key(key) {
+            Ok(())
+        } else {
+            Err(GraphError::VertexNotFound(key.clone()))
+        }
+    }
+
+    fn empty_index(&self) -> M::Map<K, M::Map<K, Edge<K, W>>> {
+        self.vertices
+            .keys()
+            .map(|key| (key.clone(), M::Map::default()))
+            .collect()
+    }
+
+    fn unlink(&mut self, key_from: &K, key_to: &K) -> Option<Edge<K, W>> {
+        let removed_edge = self.outgoing.get_mut(key_from)?.remove(key_to)?;
+        self.incoming.get_mut(key_to).unwrap().remove(key_from);
+        self.edge_count -= 1;
+
+        Some(removed_edge)
+    }
+}
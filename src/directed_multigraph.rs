@@ -0,0 +1,623 @@
+use crate::algo::Algorithms;
+use crate::any_graph::AnyGraph;
+use crate::any_graph_mut::AnyGraphMut;
+use crate::directed_algo::DirectedAlgorithms;
+use crate::kinship::Kinship;
+use crate::multigraph::Multigraph;
+use crate::types::{same_weights, EdgeId, Key, Value, Vertex, Weight};
+use crate::{Edge, GraphError};
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+
+/// An implementation of a directed graph allowing multiple edges and loops.
+/// Each edge gets its own [`EdgeId`], so one of the parallel edges between 2 keys can be
+/// targeted through [`Multigraph`], and the counts and degrees take every parallel edge into account.
+/// The vertices and edges are stored in persistent hash maps, so every new graph returned by
+/// [`AnyGraph`] shares most of its structure with the graph it was created from.
+/// The hasher of these maps, [`RandomState`] by default, can be swapped with `S`.
+pub struct DirectedMultigraph<K, V, W = (), S = RandomState>
+where
+    K: Key,
+    V: Value,
+    W: Weight,
+    S: BuildHasher,
+{
+    vertices: im::HashMap<K, Vertex<K, V>, S>,
+    edges: im::HashMap<EdgeId, Edge<K, W>, S>,
+    outgoing: im::HashMap<K, im::OrdSet<EdgeId>, S>,
+    incoming: im::HashMap<K, im::OrdSet<EdgeId>, S>,
+    next_edge_id: usize,
+}
+
+impl<K, V, W, S> AnyGraph<K, V, W> for DirectedMultigraph<K, V, W, S>
+where
+    K: Key,
+    V: Value,
+    W: Weight,
+    S: BuildHasher + Default,
+{
+    /// Iterate over the vertices of the graph.
+    /// Complexity: O(1).
+    fn vertices_iter<'a>(&'a self) -> impl ExactSizeIterator<Item = &'a Vertex<K, V>>
+    where
+        K: 'a,
+        V: 'a,
+    {
+        self.vertices.values()
+    }
+
+    /// Iterate over the keys of the vertices of the graph.
+    /// Complexity: O(1).
+    fn keys<'a>(&'a self) -> impl ExactSizeIterator<Item = &'a K>
+    where
+        K: 'a,
+    {
+        self.vertices.keys()
+    }
+
+    /// Iterate over the edges of the graph, every parallel edge included.
+    /// Complexity: O(1).
+    fn edges_iter<'a>(&'a self) -> impl ExactSizeIterator<Item = &'a Edge<K, W>>
+    where
+        K: 'a,
+        W: 'a,
+    {
+        self.edges.values()
+    }
+
+    /// Get the vertex of a given key.
+    /// Complexity: O(log V).
+    fn get_vertex(&self, key: &K) -> Option<&Vertex<K, V>> {
+        self.vertices.get(key)
+    }
+
+    /// Get the number of vertices of the graph.
+    /// Complexity: O(1).
+    fn vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+
+    /// Get the number of edges of the graph, every parallel edge included.
+    /// Complexity: O(1).
+    fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Get the number of edges to a given key, every parallel edge included,
+    /// or [`None`] if the vertex doesn't exist.
+    /// Complexity: O(log V).
+    fn in_degree(&self, key: &K) -> Option<usize> {
+        self.incoming.get(key).map(|ids| ids.len())
+    }
+
+    /// Get the number of edges from a given key, every parallel edge included,
+    /// or [`None`] if the vertex doesn't exist.
+    /// Complexity: O(log V).
+    fn out_degree(&self, key: &K) -> Option<usize> {
+        self.outgoing.get(key).map(|ids| ids.len())
+    }
+
+    /// Add a new vertex then return the graph, or fail if the key is already used.
+    /// Complexity: O(log V).
+    fn try_add_vertex(&self, vertex: Vertex<K, V>) -> Result<Self, GraphError<K>> {
+        let mut new_graph = self.clone();
+        new_graph.insert_vertex(vertex)?;
+
+        Ok(new_graph)
+    }
+
+    /// Remove all vertices then return the new graph, the deleted vertices and all the edges.
+    /// Complexity: O(V + E).
+    fn remove_all_vertices(&self) -> Option<(Self, Vec<Vertex<K, V>>, Vec<Edge<K, W>>)> {
+        let mut new_graph = self.clone();
+        let (vertices, edges) = new_graph.remove_all_vertices_mut();
+
+        Some((new_graph, vertices, edges))
+    }
+
+    /// Remove a vertex by its key then return the new graph, the deleted vertex and its edges,
+    /// or fail if the vertex doesn't exist.
+    /// Complexity: O(degree).
+    fn try_remove_vertex_where_key(
+        &self,
+        key: K,
+    ) -> Result<(Self, Vertex<K, V>, Vec<Edge<K, W>>), GraphError<K>> {
+        let mut new_graph = self.clone();
+        let (removed_vertex, removed_edges) = new_graph.remove_vertex_where_key_mut(key)?;
+
+        Ok((new_graph, removed_vertex, removed_edges))
+    }
+
+    /// Replace the value of the vertex of a given key then return the new graph and the old value,
+    /// or fail if the vertex doesn't exist.
+    /// Complexity: O(log V).
+    fn try_update_vertex_value(&self, key: K, value: V) -> Result<(Self, V), GraphError<K>> {
+        let mut new_graph = self.clone();
+        let old_value = new_graph.update_vertex_value_mut(key, value)?;
+
+        Ok((new_graph, old_value))
+    }
+
+    /// Compute a new value for every vertex from its key and its value, then return the new graph.
+    /// Complexity: O(V).
+    fn map_vertex_values<F>(&self, f: F) -> Self
+    where
+        F: FnMut(&K, &V) -> V,
+    {
+        let mut new_graph = self.clone();
+        new_graph.map_vertex_values_mut(f);
+
+        new_graph
+    }
+
+    /// Add a new parallel edge then return the new graph, or fail if an endpoint is missing.
+    /// Complexity: O(log E).
+    fn try_add_edge(&self, edge: Edge<K, W>) -> Result<Self, GraphError<K>> {
+        let mut new_graph = self.clone();
+        new_graph.insert_edge(edge)?;
+
+        Ok(new_graph)
+    }
+
+    /// Get the weight of the oldest edge between 2 keys.
+    /// Complexity: O(out-degree).
+    fn edge_weight(&self, key_from: &K, key_to: &K) -> Option<&W> {
+        let id = self.oldest_edge_id(key_from, key_to)?;
+        Some(self.edges[&id].weight())
+    }
+
+    /// Replace the weight of the oldest edge between 2 keys then return the new graph and the
+    /// old weight, or fail if there is no such edge.
+    /// Complexity: O(out-degree).
+    fn try_update_edge_weight(
+        &self,
+        key_from: K,
+        key_to: K,
+        weight: W,
+    ) -> Result<(Self, W), GraphError<K>> {
+        let mut new_graph = self.clone();
+        let old_weight = new_graph.update_edge_weight_mut(key_from, key_to, weight)?;
+
+        Ok((new_graph, old_weight))
+    }
+
+    /// Remove the oldest edge between 2 keys, then return the new graph and the deleted edge,
+    /// or fail if there is no such edge.
+    /// Complexity: O(out-degree).
+    fn try_remove_edge_where_keys(
+        &self,
+        key_from: K,
+        key_to: K,
+    ) -> Result<(Self, Edge<K, W>), GraphError<K>> {
+        let mut new_graph = self.clone();
+        let removed_edge = new_graph.remove_edge_where_keys_mut(key_from, key_to)?;
+
+        Ok((new_graph, removed_edge))
+    }
+
+    /// Remove all the edges then return the new graph and all the deleted edges.
+    /// Complexity: O(V + E).
+    fn remove_all_edges(&self) -> Option<(Self, Vec<Edge<K, W>>)> {
+        let mut new_graph = self.clone();
+        let edges = new_graph.remove_all_edges_mut();
+
+        Some((new_graph, edges))
+    }
+
+    /// Remove all existing edges from or to a given key, then return the new graph and the deleted edges,
+    /// or fail if the vertex doesn't exist.
+    /// Complexity: O(degree).
+    fn try_remove_all_edges_where_key(
+        &self,
+        key_from: K,
+    ) -> Result<(Self, Vec<Edge<K, W>>), GraphError<K>> {
+        let mut new_graph = self.clone();
+        let removed_edges = new_graph.remove_all_edges_where_key_mut(key_from)?;
+
+        Ok((new_graph, removed_edges))
+    }
+
+    /// Remove all existing edges from a given key, then return the new graph and the deleted edges,
+    /// or fail if the vertex doesn't exist.
+    /// Complexity: O(out-degree).
+    fn try_remove_all_edges_from_key(
+        &self,
+        key_from: K,
+    ) -> Result<(Self, Vec<Edge<K, W>>), GraphError<K>> {
+        let mut new_graph = self.clone();
+        let removed_edges = new_graph.remove_all_edges_from_key_mut(key_from)?;
+
+        Ok((new_graph, removed_edges))
+    }
+}
+
+impl<K, V, W, S> AnyGraphMut<K, V, W> for DirectedMultigraph<K, V, W, S>
+where
+    K: Key,
+    V: Value,
+    W: Weight,
+    S: BuildHasher + Default,
+{
+    /// Insert a new vertex, or fail if the key is already used.
+    /// Complexity: O(log V).
+    fn insert_vertex(&mut self, vertex: Vertex<K, V>) -> Result<(), GraphError<K>> {
+        if self.vertices.contains_key(vertex.key()) {
+            return Err(GraphError::DuplicateVertex(vertex.key().clone()));
+        }
+        self.outgoing
+            .insert(vertex.key().clone(), im::OrdSet::new());
+        self.incoming
+            .insert(vertex.key().clone(), im::OrdSet::new());
+        self.vertices.insert(vertex.key().clone(), vertex);
+        Ok(())
+    }
+
+    /// Remove all vertices then return the deleted vertices and all the edges.
+    /// Complexity: O(V + E).
+    fn remove_all_vertices_mut(&mut self) -> (Vec<Vertex<K, V>>, Vec<Edge<K, W>>) {
+        let edges = self.remove_all_edges_mut();
+        let vertices = std::mem::take(&mut self.vertices)
+            .into_iter()
+            .map(|(_, vertex)| vertex)
+            .collect();
+        self.outgoing = im::HashMap::default();
+        self.incoming = im::HashMap::default();
+
+        (vertices, edges)
+    }
+
+    /// Remove a vertex by its key then return the deleted vertex and its edges.
+    /// Complexity: O(degree).
+    fn remove_vertex_where_key_mut(
+        &mut self,
+        key: K,
+    ) -> Result<(Vertex<K, V>, Vec<Edge<K, W>>), GraphError<K>> {
+        let removed_edges = self.remove_all_edges_where_key_mut(key.clone())?;
+        let removed_vertex = self.vertices.remove(&key).unwrap();
+        self.outgoing.remove(&key);
+        self.incoming.remove(&key);
+
+        Ok((removed_vertex, removed_edges))
+    }
+
+    /// Replace the value of the vertex of a given key then return the old value.
+    /// Complexity: O(log V).
+    fn update_vertex_value_mut(&mut self, key: K, value: V) -> Result<V, GraphError<K>> {
+        let vertex = self
+            .vertices
+            .get_mut(&key)
+            .ok_or_else(|| GraphError::VertexNotFound(key.clone()))?;
+        let old_value = vertex.value().clone();
+        vertex.set_value(value);
+
+        Ok(old_value)
+    }
+
+    /// Compute a new value for every vertex from its key and its value.
+    /// Complexity: O(V).
+    fn map_vertex_values_mut<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&K, &V) -> V,
+    {
+        for (key, vertex) in self.vertices.iter_mut() {
+            let value = f(key, vertex.value());
+            vertex.set_value(value);
+        }
+    }
+
+    /// Insert a new parallel edge, or fail if an endpoint is missing.
+    /// Complexity: O(log E).
+    fn insert_edge(&mut self, edge: Edge<K, W>) -> Result<(), GraphError<K>> {
+        self.insert_edge_with_id(edge).map(|_| ())
+    }
+
+    /// Replace the weight of the oldest edge between 2 keys then return the old weight.
+    /// Complexity: O(out-degree).
+    fn update_edge_weight_mut(
+        &mut self,
+        key_from: K,
+        key_to: K,
+        weight: W,
+    ) -> Result<W, GraphError<K>> {
+        let id = self
+            .oldest_edge_id(&key_from, &key_to)
+            .ok_or_else(|| GraphError::EdgeNotFound(Edge::new(key_from, key_to)))?;
+
+        self.update_edge_weight_by_id_mut(id, weight)
+    }
+
+    /// Remove the oldest edge between 2 keys, then return the deleted edge.
+    /// Complexity: O(out-degree).
+    fn remove_edge_where_keys_mut(
+        &mut self,
+        key_from: K,
+        key_to: K,
+    ) -> Result<Edge<K, W>, GraphError<K>> {
+        let id = self
+            .oldest_edge_id(&key_from, &key_to)
+            .ok_or_else(|| GraphError::EdgeNotFound(Edge::new(key_from, key_to)))?;
+
+        self.remove_edge_by_id_mut(id)
+    }
+
+    /// Remove all the edges then return all the deleted edges.
+    /// Complexity: O(V + E).
+    fn remove_all_edges_mut(&mut self) -> Vec<Edge<K, W>> {
+        let removed_edges = std::mem::take(&mut self.edges)
+            .into_iter()
+            .map(|(_, edge)| edge)
+            .collect();
+        self.outgoing = self.empty_index();
+        self.incoming = self.empty_index();
+
+        removed_edges
+    }
+
+    /// Remove all existing edges from or to a given key, then return the deleted edges.
+    /// Complexity: O(degree).
+    fn remove_all_edges_where_key_mut(
+        &mut self,
+        key_from: K,
+    ) -> Result<Vec<Edge<K, W>>, GraphError<K>> {
+        let mut removed_edges = self.remove_all_edges_from_key_mut(key_from.clone())?;
+        let ids = self.incoming[&key_from].clone();
+        removed_edges.extend(ids.into_iter().filter_map(|id| self.unlink(id)));
+
+        Ok(removed_edges)
+    }
+
+    /// Remove all existing edges from a given key, then return the deleted edges.
+    /// Complexity: O(out-degree).
+    fn remove_all_edges_from_key_mut(
+        &mut self,
+        key_from: K,
+    ) -> Result<Vec<Edge<K, W>>, GraphError<K>> {
+        self.check_vertex(&key_from)?;
+        let ids = self.outgoing[&key_from].clone();
+
+        Ok(ids.into_iter().filter_map(|id| self.unlink(id)).collect())
+    }
+}
+
+impl<K, V, W, S> Multigraph<K, V, W> for DirectedMultigraph<K, V, W, S>
+where
+    K: Key,
+    V: Value,
+    W: Weight,
+    S: BuildHasher + Default,
+{
+    /// Get the edge of a given id.
+    /// Complexity: O(log E).
+    fn edge(&self, id: EdgeId) -> Option<&Edge<K, W>> {
+        self.edges.get(&id)
+    }
+
+    /// Iterate over the edges of the graph along with their ids.
+    /// Complexity: O(1).
+    fn edges_with_ids_iter<'a>(&'a self) -> impl ExactSizeIterator<Item = (EdgeId, &'a Edge<K, W>)>
+    where
+        K: 'a,
+        W: 'a,
+    {
+        self.edges.iter().map(|(id, edge)| (*id, edge))
+    }
+
+    /// Get the ids of the parallel edges between 2 keys, the oldest first.
+    /// Complexity: O(out-degree).
+    fn edge_ids_between(&self, key_from: &K, key_to: &K) -> Vec<EdgeId> {
+        match self.outgoing.get(key_from) {
+            Some(ids) => ids
+                .iter()
+                .filter(|id| self.edges[id].to() == key_to)
+                .cloned()
+                .collect(),
+            None => vec![],
+        }
+    }
+
+    /// Insert a new edge then return its id, or fail if an endpoint is missing.
+    /// Complexity: O(log E).
+    fn insert_edge_with_id(&mut self, edge: Edge<K, W>) -> Result<EdgeId, GraphError<K>> {
+        self.check_vertex(edge.from())?;
+        self.check_vertex(edge.to())?;
+
+        let id = EdgeId::new(self.next_edge_id);
+        self.next_edge_id += 1;
+        self.outgoing[edge.from()].insert(id);
+        self.incoming[edge.to()].insert(id);
+        self.edges.insert(id, edge);
+        Ok(id)
+    }
+
+    /// Replace the weight of the edge of a given id then return the old weight.
+    /// Complexity: O(log E).
+    fn update_edge_weight_by_id_mut(&mut self, id: EdgeId, weight: W) -> Result<W, GraphError<K>> {
+        let edge = self
+            .edges
+            .get_mut(&id)
+            .ok_or(GraphError::EdgeIdNotFound(id))?;
+        let old_weight = edge.weight().clone();
+        edge.set_weight(weight);
+
+        Ok(old_weight)
+    }
+
+    /// Remove the edge of a given id then return the deleted edge.
+    /// Complexity: O(log E).
+    fn remove_edge_by_id_mut(&mut self, id: EdgeId) -> Result<Edge<K, W>, GraphError<K>> {
+        self.unlink(id).ok_or(GraphError::EdgeIdNotFound(id))
+    }
+}
+
+impl<K, V, W, S> Kinship<K, V, W, S> for DirectedMultigraph<K, V, W, S>
+where
+    K: Key,
+    V: Value,
+    W: Weight,
+    S: BuildHasher + Default,
+{
+    /// Iterate over the keys of the vertices at the end of the edges from a given key,
+    /// once per parallel edge.
+    /// Complexity: O(log V).
+    fn neighbors_iter<'a>(&'a self, key: &K) -> Option<impl ExactSizeIterator<Item = &'a K>>
+    where
+        K: 'a,
+    {
+        self.outgoing
+            .get(key)
+            .map(move |ids| ids.iter().map(move |id| self.edges[id].to()))
+    }
+
+    /// Iterate over the edges from a given key, the oldest first.
+    /// Complexity: O(log V).
+    fn out_edges_iter<'a>(
+        &'a self,
+        key: &K,
+    ) -> Option<impl ExactSizeIterator<Item = &'a Edge<K, W>>>
+    where
+        K: 'a,
+        W: 'a,
+    {
+        self.outgoing
+            .get(key)
+            .map(move |ids| ids.iter().map(move |id| &self.edges[id]))
+    }
+
+    /// Iterate over the edges to a given key, the oldest first.
+    /// Complexity: O(log V).
+    fn in_edges_iter<'a>(&'a self, key: &K) -> Option<impl ExactSizeIterator<Item = &'a Edge<K, W>>>
+    where
+        K: 'a,
+        W: 'a,
+    {
+        self.incoming
+            .get(key)
+            .map(move |ids| ids.iter().map(move |id| &self.edges[id]))
+    }
+}
+
+impl<K, V, W, S> Algorithms<K, V, W, S> for DirectedMultigraph<K, V, W, S>
+where
+    K: Key,
+    V: Value,
+    W: Weight,
+    S: BuildHasher + Default,
+{
+}
+
+impl<K, V, W, S> DirectedAlgorithms<K, V, W, S> for DirectedMultigraph<K, V, W, S>
+where
+    K: Key,
+    V: Value,
+    W: Weight,
+    S: BuildHasher + Default,
+{
+}
+
+impl<K, V, W, S> Clone for DirectedMultigraph<K, V, W, S>
+where
+    K: Key,
+    V: Value,
+    W: Weight,
+    S: BuildHasher,
+{
+    fn clone(&self) -> Self {
+        DirectedMultigraph {
+            vertices: self.vertices.clone(),
+            edges: self.edges.clone(),
+            outgoing: self.outgoing.clone(),
+            incoming: self.incoming.clone(),
+            next_edge_id: self.next_edge_id,
+        }
+    }
+}
+
+impl<K, V, W, S> PartialEq for DirectedMultigraph<K, V, W, S>
+where
+    K: Key,
+    V: Value,
+    W: Weight,
+    S: BuildHasher,
+{
+    /// Two multigraphs are equal when they hold the same vertices and the same edges with the
+    /// same weights under the same ids, whatever the ids they will give next.
+    fn eq(&self, other: &Self) -> bool {
+        self.vertices == other.vertices
+            && self.edges == other.edges
+            && same_weights(
+                self.edges
+                    .iter()
+                    .map(|(id, edge)| (edge, other.edges.get(id))),
+            )
+    }
+}
+
+impl<K, V, W, S> Default for DirectedMultigraph<K, V, W, S>
+where
+    K: Key,
+    V: Value,
+    W: Weight,
+    S: BuildHasher + Default,
+{
+    fn default() -> Self {
+        DirectedMultigraph {
+            vertices: im::HashMap::default(),
+            edges: im::HashMap::default(),
+            outgoing: im::HashMap::default(),
+            incoming: im::HashMap::default(),
+            next_edge_id: 0,
+        }
+    }
+}
+
+impl<K, V, W> DirectedMultigraph<K, V, W>
+where
+    K: Key,
+    V: Value,
+    W: Weight,
+{
+    /// Create a new directed multigraph.
+    /// Complexity: O(1)
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<K, V, W, S> DirectedMultigraph<K, V, W, S>
+where
+    K: Key,
+    V: Value,
+    W: Weight,
+    S: BuildHasher + Default,
+{
+    fn check_vertex(&self, key: &K) -> Result<(), GraphError<K>> {
+        if self.vertices.contains_key(key) {
+            Ok(())
+        } else {
+            Err(GraphError::VertexNotFound(key.clone()))
+        }
+    }
+
+    fn empty_index(&self) -> im::HashMap<K, im::OrdSet<EdgeId>, S> {
+        self.vertices
+            .keys()
+            .map(|key| (key.clone(), im::OrdSet::new()))
+            .collect()
+    }
+
+    fn oldest_edge_id(&self, key_from: &K, key_to: &K) -> Option<EdgeId> {
+        self.outgoing
+            .get(key_from)?
+            .iter()
+            .find(|id| self.edges[id].to() == key_to)
+            .cloned()
+    }
+
+    fn unlink(&mut self, id: EdgeId) -> Option<Edge<K, W>> {
+        let removed_edge = self.edges.remove(&id)?;
+        self.outgoing[removed_edge.from()].remove(&id);
+        self.incoming[removed_edge.to()].remove(&id);
+
+        Some(removed_edge)
+    }
+}
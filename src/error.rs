@@ -0,0 +1,108 @@
+use crate::{Edge, EdgeId, Key};
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+
+/// An error describing why an operation on a graph failed.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum GraphError<K>
+where
+    K: Key,
+{
+    /// There is no vertex with this key in the graph.
+    VertexNotFound(K),
+    /// There is already a vertex with this key in the graph.
+    DuplicateVertex(K),
+    /// There is already an edge between these keys in the graph.
+    DuplicateEdge(Edge<K>),
+    /// There is no edge between these keys in the graph.
+    EdgeNotFound(Edge<K>),
+    /// There is no edge with this id in the graph.
+    EdgeIdNotFound(EdgeId),
+    /// This edge has a negative cost, which the algorithm doesn't support.
+    NegativeCost(Edge<K>),
+    /// These keys form a cycle of negative cost, each one leading to the next and the last one
+    /// back to the first one, so no shortest path exists through them.
+    NegativeCycle(Vec<K>),
+}
+
+impl<K> Display for GraphError<K>
+where
+    K: Key + Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GraphError::VertexNotFound(key) => write!(f, "vertex {:?} not found", key),
+            GraphError::DuplicateVertex(key) => write!(f, "vertex {:?} already exists", key),
+            GraphError::DuplicateEdge(edge) => write!(
+                f,
+                "edge from {:?} to {:?} already exists",
+                edge.from(),
+                edge.to()
+            ),
+            GraphError::EdgeNotFound(edge) => {
+                write!(
+                    f,
+                    "edge from {:?} to {:?} not found",
+                    edge.from(),
+                    edge.to()
+                )
+            }
+            GraphError::EdgeIdNotFound(id) => write!(f, "edge #{} not found", id.index()),
+            GraphError::NegativeCost(edge) => write!(
+                f,
+                "edge from {:?} to {:?} has a negative cost",
+                edge.from(),
+                edge.to()
+            ),
+            GraphError::NegativeCycle(keys) => write!(f, "negative cycle through {:?}", keys),
+        }
+    }
+}
+
+impl<K> Error for GraphError<K> where K: Key + Debug {}
+
+/// A cycle found in a graph where none was expected.
+/// The keys are given in the order of the cycle, starting from the smallest one:
+/// each key leads to the next one and the last one leads back to the first one.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Cycle<K>
+where
+    K: Key,
+{
+    keys: Vec<K>,
+}
+
+impl<K> Cycle<K>
+where
+    K: Key,
+{
+    /// Create a new cycle from its keys in order, starting from any of them.
+    pub(crate) fn new(mut keys: Vec<K>) -> Self {
+        let smallest = (0..keys.len()).min_by_key(|index| &keys[*index]);
+        if let Some(smallest) = smallest {
+            keys.rotate_left(smallest);
+        }
+        Cycle { keys }
+    }
+
+    /// Get the keys of the cycle.
+    pub fn keys(&self) -> &[K] {
+        &self.keys
+    }
+
+    /// Take the keys of the cycle.
+    pub fn into_keys(self) -> Vec<K> {
+        self.keys
+    }
+}
+
+impl<K> Display for Cycle<K>
+where
+    K: Key + Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "cycle through {:?}", self.keys)
+    }
+}
+
+impl<K> Error for Cycle<K> where K: Key + Debug {}
@@ -0,0 +1,38 @@
+/// An iterator whose exact length is known up front, such as the edges of a graph
+/// gathered from each vertex.
+pub(crate) struct Counted<I> {
+    iter: I,
+    remaining: usize,
+}
+
+impl<I> Counted<I>
+where
+    I: Iterator,
+{
+    /// Wrap an iterator yielding exactly `len` items.
+    pub(crate) fn new(iter: I, len: usize) -> Self {
+        Counted {
+            iter,
+            remaining: len,
+        }
+    }
+}
+
+impl<I> Iterator for Counted<I>
+where
+    I: Iterator,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.iter.next()?;
+        self.remaining -= 1;
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<I> ExactSizeIterator for Counted<I> where I: Iterator {}
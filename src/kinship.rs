@@ -1,46 +1,103 @@
-use crate::{AnyGraph, Edge, Key, Value, Vertex};
+use crate::{AnyGraph, Edge, Key, Value, Vertex, Weight};
 
+use std::collections::hash_map::RandomState;
 use std::collections::HashMap;
+use std::hash::BuildHasher;
 
 /// An interface for getting the successors and predecessors of each [`Vertex`].
-pub trait Kinship<K, V>: AnyGraph<K, V>
+///
+/// # Generic implementations
+/// S describe the [`BuildHasher`] used by the maps returned.
+pub trait Kinship<K, V, W = (), S = RandomState>: AnyGraph<K, V, W>
 where
     K: Key,
     V: Value,
+    W: Weight,
+    S: BuildHasher + Default,
 {
+    /// Get the keys of the vertices reached by following the edges from a given key,
+    /// or [`None`] if the vertex doesn't exist.
+    /// In a directed graph these are the destinations of the edges from the key,
+    /// in an undirected graph the keys at the opposite end of the edges touching it.
+    fn neighbors(&self, key: &K) -> Option<Vec<K>> {
+        Some(self.neighbors_iter(key)?.cloned().collect())
+    }
+
+    /// Iterate over the keys of the vertices reached by following the edges from a given key,
+    /// or [`None`] if the vertex doesn't exist.
+    /// The traversals of [`Algorithms`](crate::Algorithms) only rely on this method to move
+    /// from a vertex to the next, so it must yield the opposite end of each edge whatever the
+    /// orientation the edge is stored in.
+    fn neighbors_iter<'a>(&'a self, key: &K) -> Option<impl ExactSizeIterator<Item = &'a K>>
+    where
+        K: 'a;
+
+    /// Get the edges from a given key, or [`None`] if the vertex doesn't exist.
+    fn out_edges(&self, key: &K) -> Option<Vec<Edge<K, W>>> {
+        Some(self.out_edges_iter(key)?.cloned().collect())
+    }
+
+    /// Iterate over the edges from a given key, or [`None`] if the vertex doesn't exist.
+    fn out_edges_iter<'a>(
+        &'a self,
+        key: &K,
+    ) -> Option<impl ExactSizeIterator<Item = &'a Edge<K, W>>>
+    where
+        K: 'a,
+        W: 'a;
+
+    /// Get the edges to a given key, or [`None`] if the vertex doesn't exist.
+    fn in_edges(&self, key: &K) -> Option<Vec<Edge<K, W>>> {
+        Some(self.in_edges_iter(key)?.cloned().collect())
+    }
+
+    /// Iterate over the edges to a given key, or [`None`] if the vertex doesn't exist.
+    fn in_edges_iter<'a>(
+        &'a self,
+        key: &K,
+    ) -> Option<impl ExactSizeIterator<Item = &'a Edge<K, W>>>
+    where
+        K: 'a,
+        W: 'a;
+
     /// Get the successors of each vertex.
-    fn successors(&self) -> HashMap<Vertex<K, V>, Vec<Edge<K>>>;
+    fn successors(&self) -> HashMap<Vertex<K, V>, Vec<Edge<K, W>>, S> {
+        self.vertices_iter()
+            .map(|vertex| {
+                let edges = self.out_edges(vertex.key()).unwrap_or_default();
+                (vertex.clone(), edges)
+            })
+            .collect()
+    }
 
     /// Get the predecessors of each vertex.
-    fn predecessors(&self) -> HashMap<Vertex<K, V>, Vec<Edge<K>>>;
+    fn predecessors(&self) -> HashMap<Vertex<K, V>, Vec<Edge<K, W>>, S> {
+        self.vertices_iter()
+            .map(|vertex| {
+                let edges = self.in_edges(vertex.key()).unwrap_or_default();
+                (vertex.clone(), edges)
+            })
+            .collect()
+    }
 
     /// Get the successors of each vertex where the key is a [`Key`].
-    fn successors_as_key_and_edges(&self) -> HashMap<K, Vec<Edge<K>>> {
-        self.successors()
-            .iter()
-            .fold(HashMap::new(), |mut acc, (vertex, edges)| {
-                acc.insert(vertex.key().clone(), edges.iter().cloned().collect());
-                acc
-            })
+    fn successors_as_key_and_edges(&self) -> HashMap<K, Vec<Edge<K, W>>, S> {
+        self.keys()
+            .map(|key| (key.clone(), self.out_edges(key).unwrap_or_default()))
+            .collect()
     }
 
     /// Get the predecessors of each vertex where the key is a [`Key`].
-    fn predecessors_as_key_and_edges(&self) -> HashMap<K, Vec<Edge<K>>> {
-        self.predecessors()
-            .iter()
-            .fold(HashMap::new(), |mut acc, (vertex, edges)| {
-                acc.insert(vertex.key().clone(), edges.iter().cloned().collect());
-                acc
-            })
+    fn predecessors_as_key_and_edges(&self) -> HashMap<K, Vec<Edge<K, W>>, S> {
+        self.keys()
+            .map(|key| (key.clone(), self.in_edges(key).unwrap_or_default()))
+            .collect()
     }
 
     /// Get the map of key and vertex.
-    fn key_vertex_map(&self) -> HashMap<K, Vertex<K, V>> {
-        self.vertices()
-            .iter()
-            .fold(HashMap::new(), |mut acc, vertex| {
-                acc.insert(vertex.key().clone(), vertex.clone());
-                acc
-            })
+    fn key_vertex_map(&self) -> HashMap<K, Vertex<K, V>, S> {
+        self.vertices_iter()
+            .map(|vertex| (vertex.key().clone(), vertex.clone()))
+            .collect()
     }
 }
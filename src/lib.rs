@@ -2,21 +2,56 @@
 
 mod algo;
 mod any_graph;
+mod any_graph_mut;
 mod basic_directed_graph;
 mod basic_undirected_graph;
+mod directed_algo;
+mod directed_graph;
+mod directed_multigraph;
+mod error;
+mod iter;
 mod kinship;
+mod multigraph;
+mod ordered_directed_graph;
+mod shortest_path;
+mod storage;
+mod traversal;
 mod types;
+mod undirected_multigraph;
+mod visit;
 
 pub use self::algo::Algorithms;
+pub use self::directed_algo::DirectedAlgorithms;
 
 pub use self::any_graph::AnyGraph;
+pub use self::any_graph_mut::AnyGraphMut;
+
+pub use self::error::{Cycle, GraphError};
 
 pub use self::kinship::Kinship;
 
+pub use self::multigraph::Multigraph;
+
+pub use self::shortest_path::{AStarResult, AllPairsShortestPaths, ShortestPaths};
+
+pub use self::storage::{Hashed, Ordered, Storage, StorageMap};
+
+pub use self::traversal::{Bfs, BfsResult, Dfs, DfsResult};
+pub use self::visit::{bfs_visit, dfs_visit, BfsEvent, Control, DfsEvent};
+
+pub use self::types::Cost;
+pub use self::types::DeterministicState;
 pub use self::types::Edge;
+pub use self::types::EdgeId;
 pub use self::types::Key;
+pub use self::types::UndirectedEdge;
 pub use self::types::Value;
 pub use self::types::Vertex;
+pub use self::types::Weight;
 
 pub use self::basic_directed_graph::BasicDirectedGraph;
 pub use self::basic_undirected_graph::BasicUndirectedGraph;
+pub use self::directed_graph::DirectedGraph;
+pub use self::directed_multigraph::DirectedMultigraph;
+pub use self::ordered_directed_graph::OrderedDirectedGraph;
+pub use self::undirected_multigraph::UndirectedMultigraph;
@@ -0,0 +1,101 @@
+use crate::types::{Key, Value, Weight};
+use crate::{AnyGraphMut, Edge, EdgeId, GraphError};
+
+/// An interface for the graphs allowing multiple edges between the same keys,
+/// where each edge is told apart by an [`EdgeId`].
+///
+/// The methods of [`AnyGraph`](crate::AnyGraph) and [`AnyGraphMut`] taking 2 keys act on the
+/// oldest of the parallel edges between them, while adding an edge always adds a new parallel edge.
+///
+/// # Generic implementations
+/// K describe a type of [`Key`] to use.
+/// V describe a type of [`Value`] to store.
+/// W describe a type of [`Weight`] carried by the edges.
+#[allow(clippy::type_complexity)]
+pub trait Multigraph<K, V, W = ()>: AnyGraphMut<K, V, W>
+where
+    K: Key,
+    V: Value,
+    W: Weight,
+{
+    /// Get the edge of a given id.
+    fn edge(&self, id: EdgeId) -> Option<&Edge<K, W>>;
+
+    /// Iterate over the edges of the graph along with their ids.
+    fn edges_with_ids_iter<'a>(&'a self) -> impl ExactSizeIterator<Item = (EdgeId, &'a Edge<K, W>)>
+    where
+        K: 'a,
+        W: 'a;
+
+    /// Get the ids of the parallel edges between 2 keys, the oldest first.
+    fn edge_ids_between(&self, key_from: &K, key_to: &K) -> Vec<EdgeId>;
+
+    /// Add a new edge then return the new graph and the id of the edge,
+    /// or fail if an endpoint is missing.
+    fn try_add_edge_with_id(&self, edge: Edge<K, W>) -> Result<(Self, EdgeId), GraphError<K>> {
+        let mut new_graph = self.clone();
+        let id = new_graph.insert_edge_with_id(edge)?;
+
+        Ok((new_graph, id))
+    }
+
+    /// Insert a new edge then return its id, or fail if an endpoint is missing.
+    fn insert_edge_with_id(&mut self, edge: Edge<K, W>) -> Result<EdgeId, GraphError<K>>;
+
+    /// Replace the weight of the edge of a given id then return the new graph and the old weight,
+    /// or fail if the edge doesn't exist.
+    fn try_update_edge_weight_by_id(
+        &self,
+        id: EdgeId,
+        weight: W,
+    ) -> Result<(Self, W), GraphError<K>> {
+        let mut new_graph = self.clone();
+        let old_weight = new_graph.update_edge_weight_by_id_mut(id, weight)?;
+
+        Ok((new_graph, old_weight))
+    }
+
+    /// Replace the weight of the edge of a given id then return the old weight.
+    fn update_edge_weight_by_id_mut(&mut self, id: EdgeId, weight: W) -> Result<W, GraphError<K>>;
+
+    /// Remove the edge of a given id then return the new graph and the deleted edge,
+    /// or fail if the edge doesn't exist.
+    fn try_remove_edge_by_id(&self, id: EdgeId) -> Result<(Self, Edge<K, W>), GraphError<K>> {
+        let mut new_graph = self.clone();
+        let removed_edge = new_graph.remove_edge_by_id_mut(id)?;
+
+        Ok((new_graph, removed_edge))
+    }
+
+    /// Remove the edge of a given id then return the deleted edge.
+    fn remove_edge_by_id_mut(&mut self, id: EdgeId) -> Result<Edge<K, W>, GraphError<K>>;
+
+    /// Remove all the parallel edges between 2 keys then return the new graph and the deleted edges,
+    /// or fail if there is no such edge.
+    fn try_remove_all_edges_where_keys(
+        &self,
+        key_from: K,
+        key_to: K,
+    ) -> Result<(Self, Vec<Edge<K, W>>), GraphError<K>> {
+        let mut new_graph = self.clone();
+        let removed_edges = new_graph.remove_all_edges_where_keys_mut(key_from, key_to)?;
+
+        Ok((new_graph, removed_edges))
+    }
+
+    /// Remove all the parallel edges between 2 keys then return the deleted edges.
+    fn remove_all_edges_where_keys_mut(
+        &mut self,
+        key_from: K,
+        key_to: K,
+    ) -> Result<Vec<Edge<K, W>>, GraphError<K>> {
+        let ids = self.edge_ids_between(&key_from, &key_to);
+        if ids.is_empty() {
+            return Err(GraphError::EdgeNotFound(Edge::new(key_from, key_to)));
+        }
+
+        ids.into_iter()
+            .map(|id| self.remove_edge_by_id_mut(id))
+            .collect()
+    }
+}
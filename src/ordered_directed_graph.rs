@@ -0,0 +1,28 @@
+use crate::directed_graph::DirectedGraph;
+use crate::storage::Ordered;
+use crate::types::{Key, Value, Weight};
+
+/// A directed graph keeping its vertices and edges ordered by key.
+/// It doesn't allow multiple edges but allow loops.
+/// The vertices and edges are stored in persistent ordered maps, so the vertices, the edges,
+/// the neighbours of a vertex and every traversal always come in the order of the keys,
+/// whatever the order in which they were added.
+/// The maps returned by [`Kinship`](crate::Kinship) are built with the
+/// [`DeterministicState`](crate::DeterministicState) hasher in the order of the keys, so they
+/// iterate in the same order on every run too.
+/// Like [`BasicDirectedGraph`](crate::BasicDirectedGraph), every new graph returned by
+/// [`AnyGraph`](crate::AnyGraph) shares most of its structure with the graph it was created from.
+pub type OrderedDirectedGraph<K, V, W = ()> = DirectedGraph<K, V, W, Ordered>;
+
+impl<K, V, W> OrderedDirectedGraph<K, V, W>
+where
+    K: Key,
+    V: Value,
+    W: Weight,
+{
+    /// Create a new ordered directed graph.
+    /// Complexity: O(1)
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
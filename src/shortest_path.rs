@@ -0,0 +1,723 @@
+use crate::traversal::path_from_parents;
+use crate::{Cost, Edge, GraphError, Key, Kinship, Value, Vertex, Weight};
+use std::cmp::Ordering;
+use std::collections::hash_map::RandomState;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::hash::BuildHasher;
+use std::ops::Sub;
+
+/// The shortest paths found from a starting key to every key it reaches.
+#[derive(Clone, Debug)]
+pub struct ShortestPaths<K, C, S = RandomState>
+where
+    K: Key,
+    C: Cost,
+    S: BuildHasher,
+{
+    /// The cost of the shortest path from the starting key to each reached key.
+    pub distance: HashMap<K, C, S>,
+    /// The key before each reached key on its shortest path. The starting key has none.
+    pub predecessor: HashMap<K, K, S>,
+}
+
+impl<K, C, S> ShortestPaths<K, C, S>
+where
+    K: Key,
+    C: Cost,
+    S: BuildHasher,
+{
+    /// Get the keys on the shortest path from the starting key to a given key, both included,
+    /// or [`None`] if the key isn't reached.
+    /// Complexity: O(length of the path).
+    pub fn path_to(&self, key: &K) -> Option<Vec<K>> {
+        if !self.distance.contains_key(key) {
+            return None;
+        }
+        Some(path_from_parents(&self.predecessor, key))
+    }
+}
+
+/// The shortest paths found between every pair of keys of a graph.
+#[derive(Clone, Debug)]
+pub struct AllPairsShortestPaths<K, C, S = RandomState>
+where
+    K: Key,
+    C: Cost,
+    S: BuildHasher,
+{
+    /// Every key of the graph by increasing order, the order of the rows and columns of
+    /// [`AllPairsShortestPaths::matrix`].
+    pub keys: Vec<K>,
+    /// The cost of the shortest path from a key to another one, for each pair with a path.
+    pub distance: HashMap<(K, K), C, S>,
+    /// The key before the second key of each pair on the shortest path from the first one.
+    /// The pairs made of a key twice have none.
+    pub predecessor: HashMap<(K, K), K, S>,
+}
+
+impl<K, C, S> AllPairsShortestPaths<K, C, S>
+where
+    K: Key,
+    C: Cost,
+    S: BuildHasher,
+{
+    /// Get the keys on the shortest path from a key to another one, both included,
+    /// or [`None`] if there is no path between them.
+    /// Complexity: O(length of the path).
+    pub fn path(&self, from: &K, to: &K) -> Option<Vec<K>> {
+        if !self.distance.contains_key(&(from.clone(), to.clone())) {
+            return None;
+        }
+        let mut path = vec![to.clone()];
+        let mut current = to.clone();
+        while current != *from {
+            current = self.predecessor[&(from.clone(), current)].clone();
+            path.push(current.clone());
+        }
+        path.reverse();
+
+        Some(path)
+    }
+
+    /// Get the costs of the shortest paths as a dense matrix, the row being the key the path
+    /// starts from and the column the key it leads to, both following the order of
+    /// [`AllPairsShortestPaths::keys`], with [`None`] when there is no path.
+    /// Complexity: O(V²).
+    pub fn matrix(&self) -> Vec<Vec<Option<C>>> {
+        self.keys
+            .iter()
+            .map(|from| {
+                self.keys
+                    .iter()
+                    .map(|to| self.distance.get(&(from.clone(), to.clone())).copied())
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// A key waiting in a priority queue, the smallest score coming out first.
+/// Incomparable scores, like a NaN, are treated as equal.
+pub(crate) struct MinScored<C, K>(pub C, pub K);
+
+impl<C: PartialOrd, K> PartialEq for MinScored<C, K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<C: PartialOrd, K> Eq for MinScored<C, K> {}
+
+impl<C: PartialOrd, K> PartialOrd for MinScored<C, K> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<C: PartialOrd, K> Ord for MinScored<C, K> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.0.partial_cmp(&self.0).unwrap_or(Ordering::Equal)
+    }
+}
+
+/// Get the key at the other end of an edge followed from a given key.
+/// The edges from a key in a directed graph start from it, while the edges touching a key in an
+/// undirected graph may be stored from any of their ends.
+pub(crate) fn opposite<'e, K, W>(edge: &'e Edge<K, W>, key: &K) -> &'e K
+where
+    K: Key,
+    W: Weight,
+{
+    if edge.from() == key {
+        edge.to()
+    } else {
+        edge.from()
+    }
+}
+
+/// The result of an A* search between 2 keys.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AStarResult<K, C>
+where
+    K: Key,
+    C: Cost,
+{
+    /// The keys of the path found, both ends included, and its cost,
+    /// or [`None`] if the goal isn't reached.
+    pub path: Option<(Vec<K>, C)>,
+    /// The number of times the edges of a key were explored,
+    /// the fewer the better the heuristic guides the search.
+    pub expanded: usize,
+}
+
+/// Run Dijkstra's algorithm from an existing key, stopping once the goal is settled if any.
+/// Every edge leaving a settled key is costed before skipping the ones into settled keys,
+/// so a negative edge fails the search whether it could shorten a path or not.
+pub(crate) fn dijkstra<G, K, V, W, S, C, F>(
+    graph: &G,
+    start: &K,
+    goal: Option<&K>,
+    mut cost: F,
+) -> Result<ShortestPaths<K, C, S>, GraphError<K>>
+where
+    G: Kinship<K, V, W, S>,
+    K: Key,
+    V: Value,
+    W: Weight,
+    S: BuildHasher + Default,
+    C: Cost,
+    F: FnMut(&Vertex<K, V>, &Vertex<K, V>, &W) -> C,
+{
+    let mut distance: HashMap<K, C, S> = HashMap::default();
+    let mut predecessor: HashMap<K, K, S> = HashMap::default();
+    let mut settled: HashSet<K, S> = HashSet::default();
+    let mut heap = BinaryHeap::new();
+
+    distance.insert(start.clone(), C::default());
+    heap.push(MinScored(C::default(), start.clone()));
+
+    while let Some(MinScored(current_distance, current)) = heap.pop() {
+        if !settled.insert(current.clone()) {
+            continue;
+        }
+        if goal == Some(&current) {
+            break;
+        }
+        let current_vertex = graph.get_vertex(&current).unwrap();
+        for edge in graph.out_edges_iter(&current).unwrap() {
+            let next = opposite(edge, &current);
+            let edge_cost = non_negative_cost(
+                &mut cost,
+                current_vertex,
+                graph.get_vertex(next).unwrap(),
+                edge.weight(),
+            )?;
+            if settled.contains(next) {
+                continue;
+            }
+            let next_distance = current_distance + edge_cost;
+            if distance
+                .get(next)
+                .map_or(true, |known| next_distance < *known)
+            {
+                distance.insert(next.clone(), next_distance);
+                predecessor.insert(next.clone(), current.clone());
+                heap.push(MinScored(next_distance, next.clone()));
+            }
+        }
+    }
+
+    Ok(ShortestPaths {
+        distance,
+        predecessor,
+    })
+}
+
+/// Run the Bellman-Ford algorithm from existing keys, all starting at a cost of 0,
+/// failing with the first negative cycle found, starting from its smallest key.
+pub(crate) fn bellman_ford<'s, G, K, V, W, S, C, F, I>(
+    graph: &G,
+    starts: I,
+    mut cost: F,
+) -> Result<ShortestPaths<K, C, S>, GraphError<K>>
+where
+    G: Kinship<K, V, W, S>,
+    K: Key + 's,
+    V: Value,
+    W: Weight,
+    S: BuildHasher + Default,
+    C: Cost,
+    F: FnMut(&Vertex<K, V>, &Vertex<K, V>, &W) -> C,
+    I: IntoIterator<Item = &'s K>,
+{
+    let mut distance: HashMap<K, C, S> = HashMap::default();
+    let mut predecessor: HashMap<K, K, S> = HashMap::default();
+    let mut last_relaxed = None;
+
+    for start in starts {
+        distance.insert(start.clone(), C::default());
+    }
+
+    // the distances settle within V - 1 rounds, unless a negative cycle lowers them forever
+    for _ in 0..graph.vertex_count() {
+        last_relaxed = None;
+        for key in graph.keys() {
+            let key_distance = match distance.get(key) {
+                Some(key_distance) => *key_distance,
+                None => continue,
+            };
+            let vertex = graph.get_vertex(key).unwrap();
+            for edge in graph.out_edges_iter(key).unwrap() {
+                let next = opposite(edge, key);
+                let next_distance =
+                    key_distance + cost(vertex, graph.get_vertex(next).unwrap(), edge.weight());
+                if distance
+                    .get(next)
+                    .map_or(true, |known| next_distance < *known)
+                {
+                    distance.insert(next.clone(), next_distance);
+                    predecessor.insert(next.clone(), key.clone());
+                    last_relaxed = Some(next.clone());
+                }
+            }
+        }
+        if last_relaxed.is_none() {
+            break;
+        }
+    }
+
+    match last_relaxed {
+        Some(key) => Err(GraphError::NegativeCycle(negative_cycle(
+            &predecessor,
+            key,
+            graph.vertex_count(),
+        ))),
+        None => Ok(ShortestPaths {
+            distance,
+            predecessor,
+        }),
+    }
+}
+
+/// Get the negative cycle behind a key still relaxed after V rounds of Bellman-Ford,
+/// starting from its smallest key.
+/// The key must have been relaxed in the last round: every key relaxed has a predecessor, and
+/// going back V times from this one is sure to end up inside the cycle, never leaving the keys
+/// with a predecessor.
+fn negative_cycle<K, S>(predecessor: &HashMap<K, K, S>, key: K, vertex_count: usize) -> Vec<K>
+where
+    K: Key,
+    S: BuildHasher,
+{
+    let previous = |key: &K| {
+        predecessor
+            .get(key)
+            .expect("a key relaxed by Bellman-Ford has a predecessor")
+    };
+
+    let mut inside = key;
+    for _ in 0..vertex_count {
+        inside = previous(&inside).clone();
+    }
+
+    let mut cycle = vec![inside.clone()];
+    let mut current = previous(&inside);
+    while *current != inside {
+        cycle.push(current.clone());
+        current = previous(current);
+    }
+    cycle.reverse();
+
+    starting_from_smallest(cycle)
+}
+
+/// Get the negative cycle behind a key with a negative path to itself in Floyd-Warshall,
+/// starting from its smallest key.
+/// Each predecessor of a key was the last key of a path at least as cheap, so a cycle of
+/// predecessors is negative, and going back V times from the key is sure to end up inside one.
+fn floyd_warshall_cycle<K: Key>(keys: &[K], predecessor: &[Vec<usize>], key: usize) -> Vec<K> {
+    let predecessor = &predecessor[key];
+    let mut inside = key;
+    for _ in 0..keys.len() {
+        inside = predecessor[inside];
+    }
+
+    let mut cycle = vec![keys[inside].clone()];
+    let mut current = predecessor[inside];
+    while current != inside {
+        cycle.push(keys[current].clone());
+        current = predecessor[current];
+    }
+    cycle.reverse();
+
+    starting_from_smallest(cycle)
+}
+
+/// Rotate the keys of a cycle so that it starts from its smallest key.
+fn starting_from_smallest<K: Key>(mut cycle: Vec<K>) -> Vec<K> {
+    let smallest = (0..cycle.len()).min_by_key(|index| &cycle[*index]).unwrap();
+    cycle.rotate_left(smallest);
+    cycle
+}
+
+/// Run the A* algorithm between existing keys,
+/// opening again the keys reached through a cheaper path after their expansion.
+pub(crate) fn astar<G, K, V, W, S, C, F, H>(
+    graph: &G,
+    start: &K,
+    goal: &K,
+    mut cost: F,
+    mut heuristic: H,
+) -> Result<AStarResult<K, C>, GraphError<K>>
+where
+    G: Kinship<K, V, W, S>,
+    K: Key,
+    V: Value,
+    W: Weight,
+    S: BuildHasher + Default,
+    C: Cost,
+    F: FnMut(&Vertex<K, V>, &Vertex<K, V>, &W) -> C,
+    H: FnMut(&Vertex<K, V>, &Vertex<K, V>) -> C,
+{
+    let goal_vertex = graph.get_vertex(goal).unwrap();
+    let mut distance: HashMap<K, C, S> = HashMap::default();
+    let mut predecessor: HashMap<K, K, S> = HashMap::default();
+    let mut heap = BinaryHeap::new();
+    let mut expanded = 0;
+
+    distance.insert(start.clone(), C::default());
+    heap.push(MinScored(
+        heuristic(graph.get_vertex(start).unwrap(), goal_vertex),
+        (start.clone(), C::default()),
+    ));
+
+    while let Some(MinScored(_, (current, current_distance))) = heap.pop() {
+        if distance[&current] < current_distance {
+            continue;
+        }
+        if current == *goal {
+            let path = path_from_parents(&predecessor, goal);
+            return Ok(AStarResult {
+                path: Some((path, current_distance)),
+                expanded,
+            });
+        }
+        expanded += 1;
+        let current_vertex = graph.get_vertex(&current).unwrap();
+        for edge in graph.out_edges_iter(&current).unwrap() {
+            let next = opposite(edge, &current);
+            let next_vertex = graph.get_vertex(next).unwrap();
+            let next_distance = current_distance
+                + non_negative_cost(&mut cost, current_vertex, next_vertex, edge.weight())?;
+            if distance
+                .get(next)
+                .map_or(true, |known| next_distance < *known)
+            {
+                distance.insert(next.clone(), next_distance);
+                predecessor.insert(next.clone(), current.clone());
+                heap.push(MinScored(
+                    next_distance + heuristic(next_vertex, goal_vertex),
+                    (next.clone(), next_distance),
+                ));
+            }
+        }
+    }
+
+    Ok(AStarResult {
+        path: None,
+        expanded,
+    })
+}
+
+/// One of the 2 searches of a bidirectional A*, going forward from the start
+/// or backward from the goal.
+struct AStarSide<K, C, S> {
+    forward: bool,
+    distance: HashMap<K, C, S>,
+    parent: HashMap<K, K, S>,
+    heap: BinaryHeap<MinScored<C, (K, C)>>,
+}
+
+impl<K, C, S> AStarSide<K, C, S>
+where
+    K: Key,
+    C: Cost,
+    S: BuildHasher + Default,
+{
+    fn new(forward: bool, key: &K, score: C) -> Self {
+        let mut side = AStarSide {
+            forward,
+            distance: HashMap::default(),
+            parent: HashMap::default(),
+            heap: BinaryHeap::new(),
+        };
+        side.distance.insert(key.clone(), C::default());
+        side.heap
+            .push(MinScored(score, (key.clone(), C::default())));
+        side
+    }
+
+    /// Get the smallest score left to expand, dropping the entries of the keys reached
+    /// through a cheaper path since they were pushed.
+    fn top_score(&mut self) -> Option<C> {
+        while let Some(MinScored(score, (key, key_distance))) = self.heap.peek() {
+            if self.distance[key] < *key_distance {
+                self.heap.pop();
+            } else {
+                return Some(*score);
+            }
+        }
+        None
+    }
+}
+
+/// Run a bidirectional A* between existing keys, expanding each time the search with the fewest
+/// keys waiting, until none of them can find a path cheaper than the best one met.
+/// The heuristic must be consistent for the path to be the cheapest.
+pub(crate) fn bidirectional_astar<G, K, V, W, S, C, F, H>(
+    graph: &G,
+    start: &K,
+    goal: &K,
+    mut cost: F,
+    mut heuristic: H,
+) -> Result<AStarResult<K, C>, GraphError<K>>
+where
+    G: Kinship<K, V, W, S>,
+    K: Key,
+    V: Value,
+    W: Weight,
+    S: BuildHasher + Default,
+    C: Cost,
+    F: FnMut(&Vertex<K, V>, &Vertex<K, V>, &W) -> C,
+    H: FnMut(&Vertex<K, V>, &Vertex<K, V>) -> C,
+{
+    let start_vertex = graph.get_vertex(start).unwrap();
+    let goal_vertex = graph.get_vertex(goal).unwrap();
+    let score = heuristic(start_vertex, goal_vertex);
+    let mut forward: AStarSide<K, C, S> = AStarSide::new(true, start, score);
+    let mut backward: AStarSide<K, C, S> = AStarSide::new(false, goal, score);
+    let mut best: Option<(C, K)> = None;
+    let mut expanded = 0;
+
+    if start == goal {
+        best = Some((C::default(), start.clone()));
+    }
+
+    while let (Some(forward_score), Some(backward_score)) =
+        (forward.top_score(), backward.top_score())
+    {
+        if let Some((best_cost, _)) = &best {
+            if forward_score >= *best_cost || backward_score >= *best_cost {
+                break;
+            }
+        }
+        let (side, other) = if forward.heap.len() <= backward.heap.len() {
+            (&mut forward, &backward)
+        } else {
+            (&mut backward, &forward)
+        };
+        let MinScored(_, (current, current_distance)) = side.heap.pop().unwrap();
+        expanded += 1;
+
+        let current_vertex = graph.get_vertex(&current).unwrap();
+        let edges: Vec<&Edge<K, W>> = if side.forward {
+            graph.out_edges_iter(&current).unwrap().collect()
+        } else {
+            graph.in_edges_iter(&current).unwrap().collect()
+        };
+        for edge in edges {
+            let next = opposite(edge, &current);
+            let next_vertex = graph.get_vertex(next).unwrap();
+            let (edge_cost, score) = if side.forward {
+                (
+                    non_negative_cost(&mut cost, current_vertex, next_vertex, edge.weight())?,
+                    heuristic(next_vertex, goal_vertex),
+                )
+            } else {
+                (
+                    non_negative_cost(&mut cost, next_vertex, current_vertex, edge.weight())?,
+                    heuristic(start_vertex, next_vertex),
+                )
+            };
+            let next_distance = current_distance + edge_cost;
+            if side
+                .distance
+                .get(next)
+                .map_or(true, |known| next_distance < *known)
+            {
+                side.distance.insert(next.clone(), next_distance);
+                side.parent.insert(next.clone(), current.clone());
+                side.heap.push(MinScored(
+                    next_distance + score,
+                    (next.clone(), next_distance),
+                ));
+            }
+            if let Some(other_distance) = other.distance.get(next) {
+                let path_cost = side.distance[next] + *other_distance;
+                if best
+                    .as_ref()
+                    .map_or(true, |(best_cost, _)| path_cost < *best_cost)
+                {
+                    best = Some((path_cost, next.clone()));
+                }
+            }
+        }
+    }
+
+    let path = best.map(|(best_cost, meeting)| {
+        let mut path = path_from_parents(&forward.parent, &meeting);
+        let mut to_goal = path_from_parents(&backward.parent, &meeting);
+        to_goal.reverse();
+        path.extend(to_goal.into_iter().skip(1));
+        (path, best_cost)
+    });
+
+    Ok(AStarResult { path, expanded })
+}
+
+/// Get the cost of an edge followed from a vertex to another one, or fail if it is negative.
+fn non_negative_cost<K, V, W, C, F>(
+    cost: &mut F,
+    from: &Vertex<K, V>,
+    to: &Vertex<K, V>,
+    weight: &W,
+) -> Result<C, GraphError<K>>
+where
+    K: Key,
+    V: Value,
+    W: Weight,
+    C: Cost,
+    F: FnMut(&Vertex<K, V>, &Vertex<K, V>, &W) -> C,
+{
+    let edge_cost = cost(from, to, weight);
+    if edge_cost < C::default() {
+        return Err(GraphError::NegativeCost(Edge::new(
+            from.key().clone(),
+            to.key().clone(),
+        )));
+    }
+    Ok(edge_cost)
+}
+
+/// Run the Floyd-Warshall algorithm on the whole graph,
+/// failing with the first negative cycle found, starting from its smallest key.
+pub(crate) fn floyd_warshall<G, K, V, W, S, C, F>(
+    graph: &G,
+    mut cost: F,
+) -> Result<AllPairsShortestPaths<K, C, S>, GraphError<K>>
+where
+    G: Kinship<K, V, W, S>,
+    K: Key,
+    V: Value,
+    W: Weight,
+    S: BuildHasher + Default,
+    C: Cost,
+    F: FnMut(&Vertex<K, V>, &Vertex<K, V>, &W) -> C,
+{
+    let mut keys: Vec<K> = graph.keys().cloned().collect();
+    keys.sort();
+    let index: HashMap<&K, usize, S> = keys.iter().enumerate().map(|(i, key)| (key, i)).collect();
+    let count = keys.len();
+    let mut distance: Vec<Vec<Option<C>>> = vec![vec![None; count]; count];
+    let mut predecessor: Vec<Vec<usize>> = vec![(0..count).collect(); count];
+
+    for (i, key) in keys.iter().enumerate() {
+        distance[i][i] = Some(C::default());
+        let vertex = graph.get_vertex(key).unwrap();
+        for edge in graph.out_edges_iter(key).unwrap() {
+            let next = opposite(edge, key);
+            let j = index[next];
+            let edge_cost = cost(vertex, graph.get_vertex(next).unwrap(), edge.weight());
+            if distance[i][j].map_or(true, |known| edge_cost < known) {
+                distance[i][j] = Some(edge_cost);
+                predecessor[i][j] = i;
+            }
+        }
+    }
+
+    // a key on a negative cycle ends up with a negative path to itself, which gets twice as
+    // negative with each round, so the search stops as soon as one appears
+    let negative_cycle_key =
+        |distance: &Vec<Vec<Option<C>>>| (0..count).find(|i| distance[*i][*i] < Some(C::default()));
+    for k in 0..count {
+        if let Some(i) = negative_cycle_key(&distance) {
+            return Err(GraphError::NegativeCycle(floyd_warshall_cycle(
+                &keys,
+                &predecessor,
+                i,
+            )));
+        }
+        for i in 0..count {
+            let to_k = match distance[i][k] {
+                Some(to_k) => to_k,
+                None => continue,
+            };
+            for j in 0..count {
+                if let Some(from_k) = distance[k][j] {
+                    let through_k = to_k + from_k;
+                    if distance[i][j].map_or(true, |known| through_k < known) {
+                        distance[i][j] = Some(through_k);
+                        predecessor[i][j] = predecessor[k][j];
+                    }
+                }
+            }
+        }
+    }
+
+    if let Some(i) = negative_cycle_key(&distance) {
+        return Err(GraphError::NegativeCycle(floyd_warshall_cycle(
+            &keys,
+            &predecessor,
+            i,
+        )));
+    }
+
+    let mut paths = AllPairsShortestPaths {
+        keys: Vec::new(),
+        distance: HashMap::default(),
+        predecessor: HashMap::default(),
+    };
+    for i in 0..count {
+        for j in 0..count {
+            if let Some(cost) = distance[i][j] {
+                let pair = (keys[i].clone(), keys[j].clone());
+                if i != j {
+                    paths
+                        .predecessor
+                        .insert(pair.clone(), keys[predecessor[i][j]].clone());
+                }
+                paths.distance.insert(pair, cost);
+            }
+        }
+    }
+    paths.keys = keys;
+
+    Ok(paths)
+}
+
+/// Run Johnson's algorithm on the whole graph: Bellman-Ford from all the keys at once gives
+/// each key a potential making every cost non-negative, then Dijkstra runs from each key.
+pub(crate) fn johnson<G, K, V, W, S, C, F>(
+    graph: &G,
+    mut cost: F,
+) -> Result<AllPairsShortestPaths<K, C, S>, GraphError<K>>
+where
+    G: Kinship<K, V, W, S>,
+    K: Key,
+    V: Value,
+    W: Weight,
+    S: BuildHasher + Default,
+    C: Cost + Sub<Output = C>,
+    F: FnMut(&Vertex<K, V>, &Vertex<K, V>, &W) -> C,
+{
+    let potential = bellman_ford(graph, graph.keys(), &mut cost)?.distance;
+    let mut keys: Vec<K> = graph.keys().cloned().collect();
+    keys.sort();
+    let mut paths = AllPairsShortestPaths {
+        keys: Vec::new(),
+        distance: HashMap::default(),
+        predecessor: HashMap::default(),
+    };
+
+    for from in &keys {
+        let reweighted = dijkstra(graph, from, None, |from, to, weight| {
+            let edge_cost = cost(from, to, weight) + potential[from.key()] - potential[to.key()];
+            // the potentials only cancel out rounding errors on real numbers
+            if edge_cost < C::default() {
+                C::default()
+            } else {
+                edge_cost
+            }
+        })?;
+        for (to, reweighted_cost) in reweighted.distance {
+            let path_cost = reweighted_cost - potential[from] + potential[&to];
+            paths.distance.insert((from.clone(), to), path_cost);
+        }
+        for (to, previous) in reweighted.predecessor {
+            paths.predecessor.insert((from.clone(), to), previous);
+        }
+    }
+    paths.keys = keys;
+
+    Ok(paths)
+}
@@ -0,0 +1,200 @@
+use crate::types::{DeterministicState, Key};
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+
+/// An interface for the kind of persistent maps a [`DirectedGraph`](crate::DirectedGraph) stores
+/// its vertices and edges in.
+///
+/// # Generic implementations
+/// Hasher describe the [`BuildHasher`] used by the maps returned by [`Kinship`](crate::Kinship).
+pub trait Storage {
+    /// The map from a key to a vertex, or to the edges of a vertex.
+    type Map<K: Key, T: Clone + PartialEq>: StorageMap<K, T>;
+
+    /// The hasher of the maps returned by [`Kinship`](crate::Kinship).
+    type Hasher: BuildHasher + Default;
+}
+
+/// Store the vertices and edges in persistent hash maps built with the hasher `S`.
+pub struct Hashed<S = RandomState>(PhantomData<S>);
+
+impl<S> Storage for Hashed<S>
+where
+    S: BuildHasher + Default,
+{
+    type Map<K: Key, T: Clone + PartialEq> = im::HashMap<K, T, S>;
+    type Hasher = S;
+}
+
+/// Store the vertices and edges in persistent ordered maps, so they always come in the order
+/// of the keys.
+/// The maps returned by [`Kinship`](crate::Kinship) are built with the [`DeterministicState`]
+/// hasher in the order of the keys, so they iterate in the same order on every run too.
+pub struct Ordered;
+
+impl Storage for Ordered {
+    type Map<K: Key, T: Clone + PartialEq> = im::OrdMap<K, T>;
+    type Hasher = DeterministicState;
+}
+
+/// An interface for the operations a graph needs from the maps of a [`Storage`].
+pub trait StorageMap<K, T>: Clone + Default + PartialEq + FromIterator<(K, T)> {
+    /// Get the value of a given key.
+    fn get(&self, key: &K) -> Option<&T>;
+
+    /// Get the value of a given key to update it.
+    fn get_mut(&mut self, key: &K) -> Option<&mut T>;
+
+    /// Check if a given key has a value.
+    fn contains_key(&self, key: &K) -> bool;
+
+    /// Insert the value of a key then return its previous value.
+    fn insert(&mut self, key: K, value: T) -> Option<T>;
+
+    /// Remove a key then return its value.
+    fn remove(&mut self, key: &K) -> Option<T>;
+
+    /// Get the number of keys.
+    fn len(&self) -> usize;
+
+    /// Check if there is no key.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Iterate over the keys.
+    fn keys<'a>(&'a self) -> impl ExactSizeIterator<Item = &'a K>
+    where
+        K: 'a,
+        T: 'a;
+
+    /// Iterate over the values.
+    fn values<'a>(&'a self) -> impl ExactSizeIterator<Item = &'a T>
+    where
+        K: 'a,
+        T: 'a;
+
+    /// Update every value in place from its key.
+    fn update_values<F>(&mut self, f: F)
+    where
+        F: FnMut(&K, &mut T);
+}
+
+impl<K, T, S> StorageMap<K, T> for im::HashMap<K, T, S>
+where
+    K: Key,
+    T: Clone + PartialEq,
+    S: BuildHasher + Default,
+{
+    fn get(&self, key: &K) -> Option<&T> {
+        im::HashMap::get(self, key)
+    }
+
+    fn get_mut(&mut self, key: &K) -> Option<&mut T> {
+        im::HashMap::get_mut(self, key)
+    }
+
+    fn contains_key(&self, key: &K) -> bool {
+        im::HashMap::contains_key(self, key)
+    }
+
+    fn insert(&mut self, key: K, value: T) -> Option<T> {
+        im::HashMap::insert(self, key, value)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<T> {
+        im::HashMap::remove(self, key)
+    }
+
+    fn len(&self) -> usize {
+        im::HashMap::len(self)
+    }
+
+    fn keys<'a>(&'a self) -> impl ExactSizeIterator<Item = &'a K>
+    where
+        K: 'a,
+        T: 'a,
+    {
+        im::HashMap::keys(self)
+    }
+
+    fn values<'a>(&'a self) -> impl ExactSizeIterator<Item = &'a T>
+    where
+        K: 'a,
+        T: 'a,
+    {
+        im::HashMap::values(self)
+    }
+
+    /// Complexity: O(n).
+    fn update_values<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&K, &mut T),
+    {
+        for (key, value) in self.iter_mut() {
+            f(key, value);
+        }
+    }
+}
+
+impl<K, T> StorageMap<K, T> for im::OrdMap<K, T>
+where
+    K: Key,
+    T: Clone + PartialEq,
+{
+    fn get(&self, key: &K) -> Option<&T> {
+        im::OrdMap::get(self, key)
+    }
+
+    fn get_mut(&mut self, key: &K) -> Option<&mut T> {
+        im::OrdMap::get_mut(self, key)
+    }
+
+    fn contains_key(&self, key: &K) -> bool {
+        im::OrdMap::contains_key(self, key)
+    }
+
+    fn insert(&mut self, key: K, value: T) -> Option<T> {
+        im::OrdMap::insert(self, key, value)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<T> {
+        im::OrdMap::remove(self, key)
+    }
+
+    fn len(&self) -> usize {
+        im::OrdMap::len(self)
+    }
+
+    fn keys<'a>(&'a self) -> impl ExactSizeIterator<Item = &'a K>
+    where
+        K: 'a,
+        T: 'a,
+    {
+        im::OrdMap::keys(self)
+    }
+
+    fn values<'a>(&'a self) -> impl ExactSizeIterator<Item = &'a T>
+    where
+        K: 'a,
+        T: 'a,
+    {
+        im::OrdMap::values(self)
+    }
+
+    /// An ordered map can't be updated while iterating over it, so every key is looked up again,
+    /// updating its value in place.
+    /// Complexity: O(n log n).
+    fn update_values<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&K, &mut T),
+    {
+        let keys: Vec<K> = im::OrdMap::keys(self).cloned().collect();
+        for key in keys {
+            let value = im::OrdMap::get_mut(self, &key).unwrap();
+            f(&key, value);
+        }
+    }
+}
@@ -0,0 +1,294 @@
+use crate::{Key, Kinship, Value, Weight};
+use std::collections::hash_map::RandomState;
+use std::collections::{HashMap, VecDeque};
+use std::hash::BuildHasher;
+use std::marker::PhantomData;
+
+/// The result of a Breadth First Search from one or several starting keys.
+#[derive(Clone, Debug)]
+pub struct BfsResult<K, S = RandomState>
+where
+    K: Key,
+    S: BuildHasher,
+{
+    /// The visited keys in the order they were visited, the starting keys first.
+    pub order: Vec<K>,
+    /// The number of edges between the closest starting key and each visited key.
+    pub depth: HashMap<K, usize, S>,
+    /// The key from which each visited key was discovered. The starting keys have none.
+    pub parent: HashMap<K, K, S>,
+}
+
+/// The result of a Depth First Search from a starting key.
+/// The discovery and finish times share one clock, ticking once per event from 0.
+#[derive(Clone, Debug)]
+pub struct DfsResult<K, S = RandomState>
+where
+    K: Key,
+    S: BuildHasher,
+{
+    /// The visited keys in the order they were discovered, the starting key first.
+    pub preorder: Vec<K>,
+    /// The visited keys in the order they were finished, the starting key last.
+    pub postorder: Vec<K>,
+    /// The time at which each visited key was discovered.
+    pub discovery_time: HashMap<K, usize, S>,
+    /// The time at which all the neighbors of each visited key were explored.
+    pub finish_time: HashMap<K, usize, S>,
+}
+
+/// A lazy Breadth First Search yielding the keys in the order they are visited.
+/// Dropping it stops the search, so only the part of the graph needed is explored.
+/// It yields nothing if the starting key isn't in the graph.
+///
+/// # Generic implementations
+/// G describe the type of graph searched.
+/// K, V, W and S are the ones of the [`Kinship`] of the graph.
+pub struct Bfs<'a, G, K, V, W = (), S = RandomState>
+where
+    K: Key,
+    V: Value,
+    W: Weight,
+    S: BuildHasher + Default,
+{
+    graph: &'a G,
+    queue: VecDeque<K>,
+    depth: HashMap<K, usize, S>,
+    parent: HashMap<K, K, S>,
+    marker: PhantomData<(V, W)>,
+}
+
+/// A lazy Depth First Search yielding the keys in the order they are discovered.
+/// Dropping it stops the search, so only the part of the graph needed is explored.
+/// It yields nothing if the starting key isn't in the graph.
+///
+/// # Generic implementations
+/// G describe the type of graph searched.
+/// K, V, W and S are the ones of the [`Kinship`] of the graph.
+pub struct Dfs<'a, G, K, V, W = (), S = RandomState>
+where
+    K: Key,
+    V: Value,
+    W: Weight,
+    S: BuildHasher + Default,
+{
+    graph: &'a G,
+    start: Option<K>,
+    stack: Vec<(K, std::vec::IntoIter<&'a K>)>,
+    time: usize,
+    postorder: Vec<K>,
+    discovery_time: HashMap<K, usize, S>,
+    finish_time: HashMap<K, usize, S>,
+    marker: PhantomData<(V, W)>,
+}
+
+impl<K, S> BfsResult<K, S>
+where
+    K: Key,
+    S: BuildHasher,
+{
+    /// Get the keys on the path found from a starting key to a given key, both included,
+    /// or [`None`] if the key wasn't visited.
+    /// Complexity: O(depth).
+    pub fn path_to(&self, key: &K) -> Option<Vec<K>> {
+        if !self.depth.contains_key(key) {
+            return None;
+        }
+        Some(path_from_parents(&self.parent, key))
+    }
+}
+
+/// Get the keys on the path ending at a given key by following the parent of each key back
+/// to a key without parent.
+pub(crate) fn path_from_parents<K, S>(parent: &HashMap<K, K, S>, key: &K) -> Vec<K>
+where
+    K: Key,
+    S: BuildHasher,
+{
+    let mut path = vec![key.clone()];
+    let mut current = key;
+    while let Some(parent) = parent.get(current) {
+        path.push(parent.clone());
+        current = parent;
+    }
+    path.reverse();
+
+    path
+}
+
+impl<'a, G, K, V, W, S> Bfs<'a, G, K, V, W, S>
+where
+    G: Kinship<K, V, W, S>,
+    K: Key,
+    V: Value,
+    W: Weight,
+    S: BuildHasher + Default,
+{
+    /// Create a new Breadth First Search from a starting key.
+    /// Complexity: O(log V).
+    pub fn new(graph: &'a G, start: &K) -> Self {
+        Self::with_starts(graph, std::iter::once(start))
+    }
+
+    /// Create a new Breadth First Search from several starting keys at once, all at depth 0.
+    /// The keys not in the graph are ignored.
+    /// Complexity: O(starts * log V).
+    pub fn with_starts<'s, I>(graph: &'a G, starts: I) -> Self
+    where
+        K: 's,
+        I: IntoIterator<Item = &'s K>,
+    {
+        let mut bfs = Bfs {
+            graph,
+            queue: VecDeque::new(),
+            depth: HashMap::default(),
+            parent: HashMap::default(),
+            marker: PhantomData,
+        };
+        for start in starts {
+            if graph.contains_key(start) && !bfs.depth.contains_key(start) {
+                bfs.queue.push_back(start.clone());
+                bfs.depth.insert(start.clone(), 0);
+            }
+        }
+        bfs
+    }
+
+    /// Get the number of edges between the starting key and a discovered key.
+    /// Complexity: O(1).
+    pub fn depth(&self, key: &K) -> Option<usize> {
+        self.depth.get(key).copied()
+    }
+
+    /// Get the key from which a discovered key was discovered.
+    /// Complexity: O(1).
+    pub fn parent(&self, key: &K) -> Option<&K> {
+        self.parent.get(key)
+    }
+
+    /// Run the search to its end then return everything it found.
+    /// Complexity: O(V + E).
+    pub fn into_result(mut self) -> BfsResult<K, S> {
+        let order = self.by_ref().collect();
+        BfsResult {
+            order,
+            depth: self.depth,
+            parent: self.parent,
+        }
+    }
+}
+
+impl<'a, G, K, V, W, S> Iterator for Bfs<'a, G, K, V, W, S>
+where
+    G: Kinship<K, V, W, S>,
+    K: Key,
+    V: Value,
+    W: Weight,
+    S: BuildHasher + Default,
+{
+    type Item = K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.queue.pop_front()?;
+        let depth = self.depth[&current] + 1;
+        for neighbour in self.graph.neighbors_iter(&current).unwrap() {
+            if !self.depth.contains_key(neighbour) {
+                self.depth.insert(neighbour.clone(), depth);
+                self.parent.insert(neighbour.clone(), current.clone());
+                self.queue.push_back(neighbour.clone());
+            }
+        }
+
+        Some(current)
+    }
+}
+
+impl<'a, G, K, V, W, S> Dfs<'a, G, K, V, W, S>
+where
+    G: Kinship<K, V, W, S>,
+    K: Key,
+    V: Value,
+    W: Weight,
+    S: BuildHasher + Default,
+{
+    /// Create a new Depth First Search from a starting key.
+    /// Complexity: O(log V).
+    pub fn new(graph: &'a G, start: &K) -> Self {
+        Dfs {
+            graph,
+            start: Some(start.clone()).filter(|start| graph.contains_key(start)),
+            stack: Vec::new(),
+            time: 0,
+            postorder: Vec::new(),
+            discovery_time: HashMap::default(),
+            finish_time: HashMap::default(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Get the time at which a key was discovered.
+    /// Complexity: O(1).
+    pub fn discovery_time(&self, key: &K) -> Option<usize> {
+        self.discovery_time.get(key).copied()
+    }
+
+    /// Get the time at which a key was finished.
+    /// Complexity: O(1).
+    pub fn finish_time(&self, key: &K) -> Option<usize> {
+        self.finish_time.get(key).copied()
+    }
+
+    /// Run the search to its end then return everything it found.
+    /// Complexity: O(V + E).
+    pub fn into_result(mut self) -> DfsResult<K, S> {
+        let preorder = self.by_ref().collect();
+        DfsResult {
+            preorder,
+            postorder: self.postorder,
+            discovery_time: self.discovery_time,
+            finish_time: self.finish_time,
+        }
+    }
+
+    fn discover(&mut self, key: K) {
+        self.discovery_time.insert(key.clone(), self.time);
+        self.time += 1;
+        let neighbors: Vec<&'a K> = self.graph.neighbors_iter(&key).unwrap().collect();
+        self.stack.push((key, neighbors.into_iter()));
+    }
+}
+
+impl<'a, G, K, V, W, S> Iterator for Dfs<'a, G, K, V, W, S>
+where
+    G: Kinship<K, V, W, S>,
+    K: Key,
+    V: Value,
+    W: Weight,
+    S: BuildHasher + Default,
+{
+    type Item = K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(start) = self.start.take() {
+            self.discover(start.clone());
+            return Some(start);
+        }
+        loop {
+            let (_, neighbors) = self.stack.last_mut()?;
+            match neighbors.next() {
+                Some(neighbour) => {
+                    if !self.discovery_time.contains_key(neighbour) {
+                        self.discover(neighbour.clone());
+                        return Some(neighbour.clone());
+                    }
+                }
+                None => {
+                    let (current, _) = self.stack.pop().unwrap();
+                    self.finish_time.insert(current.clone(), self.time);
+                    self.time += 1;
+                    self.postorder.push(current);
+                }
+            }
+        }
+    }
+}
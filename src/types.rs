@@ -1,13 +1,35 @@
 use std::cmp::Ordering;
-use std::hash::{Hash, Hasher};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{BuildHasherDefault, Hash, Hasher};
+use std::ops::Add;
 
 /// An interface used as an helper to implement a key.
-pub trait Key: Clone + Copy + PartialEq + Eq + PartialOrd + Ord + Hash {}
-impl<T: Copy + Hash + Ord> Key for T {}
+pub trait Key: Clone + PartialEq + Eq + PartialOrd + Ord + Hash {}
+impl<T: Clone + Hash + Ord> Key for T {}
 
 /// An interface used as an helper to implement a value contained in a vertex.
-pub trait Value: Clone + Copy + Default + PartialEq {}
-impl<T: Copy + Default + PartialEq> Value for T {}
+pub trait Value: Clone + PartialEq {}
+impl<T: Clone + PartialEq> Value for T {}
+
+/// An interface used as an helper to implement a weight carried by an edge.
+pub trait Weight: Clone + PartialEq {}
+impl<T: Clone + PartialEq> Weight for T {}
+
+/// An interface used as an helper to implement the cost of a path, summed edge after edge.
+/// The default value is the cost of an empty path.
+pub trait Cost: Copy + PartialOrd + Add<Output = Self> + Default {}
+impl<T: Copy + PartialOrd + Add<Output = T> + Default> Cost for T {}
+
+/// A [`BuildHasher`](std::hash::BuildHasher) hashing with fixed keys, so hash maps filled in the
+/// same order iterate in the same order on every run.
+/// The maps returned by the [`Kinship`](crate::Kinship) methods of an
+/// [`OrderedDirectedGraph`](crate::OrderedDirectedGraph) are built with it.
+pub type DeterministicState = BuildHasherDefault<DefaultHasher>;
+
+/// An identifier telling apart the parallel edges of a multigraph.
+/// The ids are given in increasing order and never reused by the graph that gave them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EdgeId(usize);
 
 /// A structure describing a vertex with a [`Key`] and a [`Value`].
 #[derive(Clone, Copy, Debug)]
@@ -20,14 +42,33 @@ where
     value: V,
 }
 
-/// A structure describing an edge with an origin [`Key`] and destination [`Key`].
-#[derive(Clone, Copy, Hash, Eq, PartialEq, Ord, PartialOrd, Debug)]
-pub struct Edge<K>
+/// A structure describing an edge with an origin [`Key`], a destination [`Key`] and a [`Weight`].
+/// Two edges are equal when they share the same origin and destination, whatever their weight:
+/// a graph holds at most one edge from a key to another, so these keys alone identify the edge
+/// to look up or to remove.
+/// Hashing and ordering follow the same rule, and the graphs compare the weights of their edges
+/// on their own.
+#[derive(Clone, Copy, Debug)]
+pub struct Edge<K, W = ()>
 where
     K: Key,
+    W: Weight,
 {
     from: K,
     to: K,
+    weight: W,
+}
+
+/// A structure describing an edge of an undirected graph with 2 [`Key`] and a [`Weight`].
+/// The keys are kept in increasing order, so two undirected edges joining the same keys are equal,
+/// hash and compare the same whatever the order they were given in and whatever their weight.
+#[derive(Clone, Copy, Debug)]
+pub struct UndirectedEdge<K, W = ()>
+where
+    K: Key,
+    W: Weight,
+{
+    edge: Edge<K, W>,
 }
 
 impl<K, V> Hash for Vertex<K, V>
@@ -63,7 +104,7 @@ where
     V: Value,
 {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.key.partial_cmp(other.key())
+        Some(self.cmp(other))
     }
 }
 
@@ -77,10 +118,107 @@ where
     }
 }
 
+impl<K, W> Hash for Edge<K, W>
+where
+    K: Key,
+    W: Weight,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.from.hash(state);
+        self.to.hash(state);
+    }
+}
+
+impl<K, W> PartialEq for Edge<K, W>
+where
+    K: Key,
+    W: Weight,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.from.eq(other.from()) && self.to.eq(other.to())
+    }
+}
+
+impl<K, W> Eq for Edge<K, W>
+where
+    K: Key,
+    W: Weight,
+{
+}
+
+impl<K, W> PartialOrd for Edge<K, W>
+where
+    K: Key,
+    W: Weight,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K, W> Ord for Edge<K, W>
+where
+    K: Key,
+    W: Weight,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.from
+            .cmp(other.from())
+            .then_with(|| self.to.cmp(other.to()))
+    }
+}
+
+impl<K, W> Hash for UndirectedEdge<K, W>
+where
+    K: Key,
+    W: Weight,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.edge.hash(state);
+    }
+}
+
+impl<K, W> PartialEq for UndirectedEdge<K, W>
+where
+    K: Key,
+    W: Weight,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.edge.eq(&other.edge)
+    }
+}
+
+impl<K, W> Eq for UndirectedEdge<K, W>
+where
+    K: Key,
+    W: Weight,
+{
+}
+
+impl<K, W> PartialOrd for UndirectedEdge<K, W>
+where
+    K: Key,
+    W: Weight,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K, W> Ord for UndirectedEdge<K, W>
+where
+    K: Key,
+    W: Weight,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.edge.cmp(&other.edge)
+    }
+}
+
 impl<K, V> Vertex<K, V>
 where
     K: Key,
-    V: Value,
+    V: Value + Default,
 {
     /// Create a new vertex with the default value.
     pub fn new(key: K) -> Self {
@@ -89,7 +227,13 @@ where
             value: V::default(),
         }
     }
+}
 
+impl<K, V> Vertex<K, V>
+where
+    K: Key,
+    V: Value,
+{
     /// Create a new vertex with a value.
     pub fn with_value(key: K, value: V) -> Self {
         Vertex { key, value }
@@ -111,13 +255,29 @@ where
     }
 }
 
-impl<K> Edge<K>
+impl<K, W> Edge<K, W>
 where
     K: Key,
+    W: Weight + Default,
 {
-    /// Create a new edge.
+    /// Create a new edge with the default weight.
     pub fn new(from: K, to: K) -> Self {
-        Edge { from, to }
+        Edge {
+            from,
+            to,
+            weight: W::default(),
+        }
+    }
+}
+
+impl<K, W> Edge<K, W>
+where
+    K: Key,
+    W: Weight,
+{
+    /// Create a new edge with a weight.
+    pub fn with_weight(from: K, to: K, weight: W) -> Self {
+        Edge { from, to, weight }
     }
 
     /// Get the origin.
@@ -129,4 +289,129 @@ where
     pub fn to(&self) -> &K {
         &self.to
     }
+
+    /// Get the weight of the edge.
+    pub fn weight(&self) -> &W {
+        &self.weight
+    }
+
+    /// Set the weight of the edge.
+    pub fn set_weight(&mut self, weight: W) {
+        self.weight = weight
+    }
+}
+
+impl<K, W> UndirectedEdge<K, W>
+where
+    K: Key,
+    W: Weight + Default,
+{
+    /// Create a new undirected edge with the default weight.
+    pub fn new(key_a: K, key_b: K) -> Self {
+        Self::with_weight(key_a, key_b, W::default())
+    }
+}
+
+impl<K, W> UndirectedEdge<K, W>
+where
+    K: Key,
+    W: Weight,
+{
+    /// Create a new undirected edge with a weight.
+    pub fn with_weight(key_a: K, key_b: K, weight: W) -> Self {
+        let edge = if key_a <= key_b {
+            Edge::with_weight(key_a, key_b, weight)
+        } else {
+            Edge::with_weight(key_b, key_a, weight)
+        };
+        UndirectedEdge { edge }
+    }
+
+    /// Get the 2 keys joined by the edge, the smallest first.
+    pub fn endpoints(&self) -> (&K, &K) {
+        (self.edge.from(), self.edge.to())
+    }
+
+    /// Check if one of the keys joined by the edge is a given key.
+    pub fn contains(&self, key: &K) -> bool {
+        self.edge.from() == key || self.edge.to() == key
+    }
+
+    /// Get the key at the other end of the edge from a given key,
+    /// or [`None`] if the edge doesn't touch it.
+    pub fn other(&self, key: &K) -> Option<&K> {
+        if self.edge.from() == key {
+            Some(self.edge.to())
+        } else if self.edge.to() == key {
+            Some(self.edge.from())
+        } else {
+            None
+        }
+    }
+
+    /// Check if the edge joins a key to itself.
+    pub fn is_loop(&self) -> bool {
+        self.edge.from() == self.edge.to()
+    }
+
+    /// Get the weight of the edge.
+    pub fn weight(&self) -> &W {
+        self.edge.weight()
+    }
+
+    /// Set the weight of the edge.
+    pub fn set_weight(&mut self, weight: W) {
+        self.edge.set_weight(weight)
+    }
+
+    /// Get the edge as an [`Edge`] going from the smallest key to the other one.
+    pub fn as_edge(&self) -> &Edge<K, W> {
+        &self.edge
+    }
+}
+
+impl<K, W> From<Edge<K, W>> for UndirectedEdge<K, W>
+where
+    K: Key,
+    W: Weight,
+{
+    fn from(edge: Edge<K, W>) -> Self {
+        UndirectedEdge::with_weight(edge.from, edge.to, edge.weight)
+    }
+}
+
+impl<K, W> From<UndirectedEdge<K, W>> for Edge<K, W>
+where
+    K: Key,
+    W: Weight,
+{
+    fn from(edge: UndirectedEdge<K, W>) -> Self {
+        edge.edge
+    }
+}
+
+impl EdgeId {
+    /// Create an id from its index.
+    pub(crate) fn new(index: usize) -> Self {
+        EdgeId(index)
+    }
+
+    /// Get the index of the id.
+    pub fn index(&self) -> usize {
+        self.0
+    }
+}
+
+/// Check if each edge carries the same weight as its counterpart in another graph, [`None`]
+/// standing for a counterpart missing from the other graph.
+/// Edges are equal whatever their weight, so the graphs storing [`Edge`]s compare the weights
+/// with this helper on top of comparing their edges.
+pub(crate) fn same_weights<'a, K, W>(
+    mut pairs: impl Iterator<Item = (&'a Edge<K, W>, Option<&'a Edge<K, W>>)>,
+) -> bool
+where
+    K: Key + 'a,
+    W: Weight + 'a,
+{
+    pairs.all(|(edge, counterpart)| counterpart.map(Edge::weight) == Some(edge.weight()))
 }
@@ -0,0 +1,272 @@
+use crate::{GraphError, Key, Kinship, Value, Weight};
+use std::collections::{HashSet, VecDeque};
+use std::hash::BuildHasher;
+
+/// An event met by a Depth First Search, given to the visitor of [`dfs_visit`].
+/// The edges are given as the key they are followed from, then the key they lead to.
+///
+/// In an undirected graph every tree edge is also met from its other end,
+/// where it is reported as a [`DfsEvent::BackEdge`] to the parent.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DfsEvent<'k, K>
+where
+    K: Key,
+{
+    /// A key is reached for the first time.
+    Discover(&'k K),
+    /// An edge leads to a key not discovered yet, which is going to be discovered through it.
+    TreeEdge(&'k K, &'k K),
+    /// An edge leads to a key discovered but not finished yet, an ancestor in the search tree.
+    BackEdge(&'k K, &'k K),
+    /// An edge leads to a key already finished, either a descendant or in another subtree.
+    CrossForwardEdge(&'k K, &'k K),
+    /// All the neighbors of a key were explored.
+    Finish(&'k K),
+}
+
+/// An event met by a Breadth First Search, given to the visitor of [`bfs_visit`].
+/// The edges are given as the key they are followed from, then the key they lead to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BfsEvent<'k, K>
+where
+    K: Key,
+{
+    /// A key is reached for the first time and queued.
+    Discover(&'k K),
+    /// An edge leads to a key not discovered yet, which is going to be discovered through it.
+    TreeEdge(&'k K, &'k K),
+    /// An edge leads to a key already discovered.
+    NonTreeEdge(&'k K, &'k K),
+    /// All the neighbors of a key were explored.
+    Finish(&'k K),
+}
+
+/// What a visitor asks the search to do after an event.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Control {
+    /// Go on with the search.
+    Continue,
+    /// Skip what is beyond the event: the neighbors of a discovered key are not explored,
+    /// and the key at the end of a tree edge is not discovered through it.
+    /// It acts like [`Control::Continue`] for the other events.
+    Prune,
+    /// Stop the search right away.
+    Break,
+}
+
+/// Execute a Depth First Search from a starting key on any graph, giving each event met to a visitor,
+/// then return [`Control::Break`] if the visitor stopped the search or [`Control::Continue`] otherwise,
+/// or fail if the vertex doesn't exist.
+/// Complexity: O(V + E).
+pub fn dfs_visit<G, K, V, W, S, F>(
+    graph: &G,
+    start: &K,
+    mut visitor: F,
+) -> Result<Control, GraphError<K>>
+where
+    G: Kinship<K, V, W, S>,
+    K: Key,
+    V: Value,
+    W: Weight,
+    S: BuildHasher + Default,
+    F: FnMut(DfsEvent<K>) -> Control,
+{
+    if !graph.contains_key(start) {
+        return Err(GraphError::VertexNotFound(start.clone()));
+    }
+    let mut discovered: HashSet<K, S> = HashSet::default();
+    let mut finished: HashSet<K, S> = HashSet::default();
+
+    Ok(dfs_visit_from(
+        graph,
+        start,
+        &mut discovered,
+        &mut finished,
+        &mut visitor,
+    ))
+}
+
+/// Execute a Breadth First Search from a starting key on any graph, giving each event met to a visitor,
+/// then return [`Control::Break`] if the visitor stopped the search or [`Control::Continue`] otherwise,
+/// or fail if the vertex doesn't exist.
+/// Complexity: O(V + E).
+pub fn bfs_visit<G, K, V, W, S, F>(
+    graph: &G,
+    start: &K,
+    mut visitor: F,
+) -> Result<Control, GraphError<K>>
+where
+    G: Kinship<K, V, W, S>,
+    K: Key,
+    V: Value,
+    W: Weight,
+    S: BuildHasher + Default,
+    F: FnMut(BfsEvent<K>) -> Control,
+{
+    if !graph.contains_key(start) {
+        return Err(GraphError::VertexNotFound(start.clone()));
+    }
+    let mut discovered: HashSet<K, S> = HashSet::default();
+
+    Ok(bfs_visit_from(
+        graph,
+        std::iter::once(start),
+        &mut discovered,
+        &mut visitor,
+    ))
+}
+
+/// Run a Depth First Search from an existing key not discovered yet, sharing the keys
+/// already discovered and finished with the previous searches.
+pub(crate) fn dfs_visit_from<G, K, V, W, S, F>(
+    graph: &G,
+    start: &K,
+    discovered: &mut HashSet<K, S>,
+    finished: &mut HashSet<K, S>,
+    visitor: &mut F,
+) -> Control
+where
+    G: Kinship<K, V, W, S>,
+    K: Key,
+    V: Value,
+    W: Weight,
+    S: BuildHasher + Default,
+    F: FnMut(DfsEvent<K>) -> Control,
+{
+    let mut stack = Vec::new();
+
+    discovered.insert(start.clone());
+    match visitor(DfsEvent::Discover(start)) {
+        Control::Break => return Control::Break,
+        Control::Prune => {
+            finished.insert(start.clone());
+            return match visitor(DfsEvent::Finish(start)) {
+                Control::Break => Control::Break,
+                _ => Control::Continue,
+            };
+        }
+        Control::Continue => stack.push(dfs_frame(graph, start)),
+    }
+
+    while !stack.is_empty() {
+        let top = stack.len() - 1;
+        let next = stack[top].1.next();
+        let current = &stack[top].0;
+        match next {
+            Some(neighbour) if !discovered.contains(neighbour) => {
+                match visitor(DfsEvent::TreeEdge(current, neighbour)) {
+                    Control::Break => return Control::Break,
+                    Control::Prune => continue,
+                    Control::Continue => {}
+                }
+                discovered.insert(neighbour.clone());
+                match visitor(DfsEvent::Discover(neighbour)) {
+                    Control::Break => return Control::Break,
+                    Control::Prune => {
+                        finished.insert(neighbour.clone());
+                        if visitor(DfsEvent::Finish(neighbour)) == Control::Break {
+                            return Control::Break;
+                        }
+                    }
+                    Control::Continue => stack.push(dfs_frame(graph, neighbour)),
+                }
+            }
+            Some(neighbour) => {
+                let event = if finished.contains(neighbour) {
+                    DfsEvent::CrossForwardEdge(current, neighbour)
+                } else {
+                    DfsEvent::BackEdge(current, neighbour)
+                };
+                if visitor(event) == Control::Break {
+                    return Control::Break;
+                }
+            }
+            None => {
+                let (current, _) = stack.pop().unwrap();
+                let control = visitor(DfsEvent::Finish(&current));
+                finished.insert(current);
+                if control == Control::Break {
+                    return Control::Break;
+                }
+            }
+        }
+    }
+    Control::Continue
+}
+
+/// Run a Breadth First Search from existing keys, skipping the ones already discovered by
+/// the previous searches sharing the same set.
+pub(crate) fn bfs_visit_from<'s, G, K, V, W, S, F, I>(
+    graph: &G,
+    starts: I,
+    discovered: &mut HashSet<K, S>,
+    visitor: &mut F,
+) -> Control
+where
+    G: Kinship<K, V, W, S>,
+    K: Key + 's,
+    V: Value,
+    W: Weight,
+    S: BuildHasher + Default,
+    F: FnMut(BfsEvent<K>) -> Control,
+    I: IntoIterator<Item = &'s K>,
+{
+    let mut queue: VecDeque<K> = VecDeque::new();
+
+    for start in starts {
+        if !discovered.insert(start.clone()) {
+            continue;
+        }
+        match visitor(BfsEvent::Discover(start)) {
+            Control::Break => return Control::Break,
+            Control::Prune => {
+                if visitor(BfsEvent::Finish(start)) == Control::Break {
+                    return Control::Break;
+                }
+            }
+            Control::Continue => queue.push_back(start.clone()),
+        }
+    }
+
+    while let Some(current) = queue.pop_front() {
+        for neighbour in graph.neighbors_iter(&current).unwrap() {
+            if discovered.contains(neighbour) {
+                if visitor(BfsEvent::NonTreeEdge(&current, neighbour)) == Control::Break {
+                    return Control::Break;
+                }
+                continue;
+            }
+            match visitor(BfsEvent::TreeEdge(&current, neighbour)) {
+                Control::Break => return Control::Break,
+                Control::Prune => continue,
+                Control::Continue => {}
+            }
+            discovered.insert(neighbour.clone());
+            match visitor(BfsEvent::Discover(neighbour)) {
+                Control::Break => return Control::Break,
+                Control::Prune => {
+                    if visitor(BfsEvent::Finish(neighbour)) == Control::Break {
+                        return Control::Break;
+                    }
+                }
+                Control::Continue => queue.push_back(neighbour.clone()),
+            }
+        }
+        if visitor(BfsEvent::Finish(&current)) == Control::Break {
+            return Control::Break;
+        }
+    }
+    Control::Continue
+}
+
+fn dfs_frame<'a, G, K, V, W, S>(graph: &'a G, key: &K) -> (K, std::vec::IntoIter<&'a K>)
+where
+    G: Kinship<K, V, W, S>,
+    K: Key,
+    V: Value,
+    W: Weight,
+    S: BuildHasher + Default,
+{
+    let neighbors: Vec<&'a K> = graph.neighbors_iter(key).unwrap().collect();
+    (key.clone(), neighbors.into_iter())
+}
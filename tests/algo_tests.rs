@@ -7,7 +7,12 @@ mod utils;
 #[cfg(test)]
 mod algo_tests {
     use crate::utils::assert_sorted_vec_eq;
-    use graphific::{Algorithms, AnyGraph, BasicDirectedGraph, BasicUndirectedGraph, Edge, Vertex};
+    use graphific::{
+        bfs_visit, dfs_visit, Algorithms, AnyGraph, AnyGraphMut, BasicDirectedGraph,
+        BasicUndirectedGraph, Bfs, BfsEvent, Control, Dfs, DfsEvent, DirectedAlgorithms,
+        DirectedMultigraph, Edge, GraphError, Multigraph, OrderedDirectedGraph,
+        UndirectedMultigraph, Vertex,
+    };
 
     fn init_bdg() -> BasicDirectedGraph<i32, i32> {
         let bdg: BasicDirectedGraph<i32, i32> = BasicDirectedGraph::new();
@@ -102,6 +107,7 @@ mod algo_tests {
         assert_sorted_vec_eq(&expected_dg.edges(), &result_dg.edges());
         assert_eq!(true, result_dg.eq(&expected_dg));
 
+        // the edge added as (4, 1) is stored as (1, 4), so 4 is reached straight from 1
         let expected_ug: BasicUndirectedGraph<i32, i32> = BasicUndirectedGraph::new()
             .add_vertex(Vertex::new(1))
             .unwrap()
@@ -115,7 +121,7 @@ mod algo_tests {
             .unwrap()
             .add_edge(Edge::new(1, 3))
             .unwrap()
-            .add_edge(Edge::new(3, 4))
+            .add_edge(Edge::new(4, 1))
             .unwrap();
         let result_ug = bug.bfs_with_starting_vertex(&start_vertex).unwrap();
         assert_eq!(true, result_ug.eq(&expected_ug));
@@ -170,6 +176,7 @@ mod algo_tests {
         assert_sorted_vec_eq(&expected_dg.edges(), &result_dg.edges());
         assert_eq!(true, result_dg.eq(&expected_dg));
 
+        // the edge added as (4, 1) is stored as (1, 4), so 4 is reached straight from 1
         let expected_ug: BasicUndirectedGraph<i32, i32> = BasicUndirectedGraph::new()
             .add_vertex(Vertex::new(1))
             .unwrap()
@@ -183,9 +190,950 @@ mod algo_tests {
             .unwrap()
             .add_edge(Edge::new(1, 3))
             .unwrap()
-            .add_edge(Edge::new(3, 4))
+            .add_edge(Edge::new(4, 1))
             .unwrap();
         let result_ug = bug.dfs_with_starting_vertex(&start_vertex).unwrap();
         assert_eq!(true, result_ug.eq(&expected_ug));
     }
+
+    #[test]
+    fn bfs_with_non_copy_keys() {
+        let graph: BasicDirectedGraph<String, i32> = BasicDirectedGraph::new()
+            .add_vertex(Vertex::new(String::from("a")))
+            .unwrap()
+            .add_vertex(Vertex::new(String::from("b")))
+            .unwrap()
+            .add_vertex(Vertex::new(String::from("c")))
+            .unwrap()
+            .add_edge(Edge::new(String::from("a"), String::from("b")))
+            .unwrap()
+            .add_edge(Edge::new(String::from("b"), String::from("c")))
+            .unwrap()
+            .add_edge(Edge::new(String::from("c"), String::from("a")))
+            .unwrap();
+
+        let start_vertex: Vertex<String, i32> = Vertex::new(String::from("a"));
+        let result = graph.bfs_with_starting_vertex(&start_vertex).unwrap();
+        let expected_edges: Vec<Edge<String>> = vec![
+            Edge::new(String::from("a"), String::from("b")),
+            Edge::new(String::from("b"), String::from("c")),
+        ];
+        assert_sorted_vec_eq(&expected_edges, &result.edges());
+    }
+
+    #[test]
+    fn ordered_traversals() {
+        let mut graph: OrderedDirectedGraph<i32, i32> = OrderedDirectedGraph::new();
+        for key in [4, 3, 2, 1] {
+            graph = graph.add_vertex(Vertex::new(key)).unwrap();
+        }
+        for (key_from, key_to) in [(4, 1), (3, 4), (2, 4), (1, 3), (1, 2)] {
+            graph = graph.add_edge(Edge::new(key_from, key_to)).unwrap();
+        }
+
+        // the traversals start from the smallest key and follow the neighbours in order
+        let result = graph.bfs().unwrap();
+        let expected_edges = vec![Edge::new(1, 2), Edge::new(1, 3), Edge::new(2, 4)];
+        assert_eq!(expected_edges, result.edges());
+        assert_eq!(true, result.eq(&graph.bfs().unwrap()));
+
+        let result = graph.dfs().unwrap();
+        let expected_edges = vec![Edge::new(1, 2), Edge::new(1, 3), Edge::new(3, 4)];
+        assert_eq!(expected_edges, result.edges());
+        assert_eq!(true, result.eq(&graph.dfs().unwrap()));
+    }
+
+    #[test]
+    fn undirected_traversals() {
+        let mut graph: BasicUndirectedGraph<i32, i32, u32> = BasicUndirectedGraph::new();
+        for key in [1, 2, 3, 4] {
+            graph = graph.add_vertex(Vertex::new(key)).unwrap();
+        }
+        for (key_a, key_b, weight) in [(1, 2, 12), (2, 3, 23), (4, 3, 34)] {
+            graph = graph
+                .add_edge(Edge::with_weight(key_a, key_b, weight))
+                .unwrap();
+        }
+
+        // every edge is followed both ways, keeping its weight
+        let start_vertex: Vertex<i32, i32> = Vertex::new(4);
+        for result in [
+            graph.bfs_with_starting_vertex(&start_vertex).unwrap(),
+            graph.dfs_with_starting_vertex(&start_vertex).unwrap(),
+        ] {
+            assert_eq!(true, result.eq(&graph));
+            assert_eq!(Some(&12), result.edge_weight(&2, &1));
+        }
+
+        let mut multigraph: UndirectedMultigraph<i32, i32> = UndirectedMultigraph::new();
+        for key in [1, 2, 3] {
+            multigraph = multigraph.add_vertex(Vertex::new(key)).unwrap();
+        }
+        for (key_a, key_b) in [(1, 2), (2, 1), (3, 2)] {
+            multigraph = multigraph.add_edge(Edge::new(key_a, key_b)).unwrap();
+        }
+        let start_vertex: Vertex<i32, i32> = Vertex::new(3);
+        let result = multigraph.bfs_with_starting_vertex(&start_vertex).unwrap();
+        assert_sorted_vec_eq(&vec![Edge::new(1, 2), Edge::new(2, 3)], &result.edges());
+    }
+
+    fn init_odg() -> OrderedDirectedGraph<i32, i32> {
+        let mut graph: OrderedDirectedGraph<i32, i32> = OrderedDirectedGraph::new();
+        for key in 1..=6 {
+            graph = graph.add_vertex(Vertex::new(key)).unwrap();
+        }
+        for (key_from, key_to) in [(1, 2), (1, 3), (2, 4), (3, 4), (4, 5), (5, 2)] {
+            graph = graph.add_edge(Edge::new(key_from, key_to)).unwrap();
+        }
+        graph
+    }
+
+    #[test]
+    fn bfs_result() {
+        let graph = init_odg();
+
+        let result = graph.bfs_from(&1).unwrap();
+        assert_eq!(vec![1, 2, 3, 4, 5], result.order);
+        assert_eq!(Some(&0), result.depth.get(&1));
+        assert_eq!(Some(&2), result.depth.get(&4));
+        assert_eq!(Some(&3), result.depth.get(&5));
+        assert_eq!(None, result.depth.get(&6));
+        assert_eq!(None, result.parent.get(&1));
+        assert_eq!(Some(&2), result.parent.get(&4));
+        assert_eq!(Some(vec![1, 2, 4, 5]), result.path_to(&5));
+        assert_eq!(Some(vec![1]), result.path_to(&1));
+        assert_eq!(None, result.path_to(&6));
+
+        let result = graph.bfs_from(&5).unwrap();
+        assert_eq!(vec![5, 2, 4], result.order);
+        assert_eq!(true, graph.bfs_from(&7).is_err());
+    }
+
+    #[test]
+    fn dfs_result() {
+        let graph = init_odg();
+
+        let result = graph.dfs_from(&1).unwrap();
+        assert_eq!(vec![1, 2, 4, 5, 3], result.preorder);
+        assert_eq!(vec![5, 4, 2, 3, 1], result.postorder);
+        assert_eq!(Some(&0), result.discovery_time.get(&1));
+        assert_eq!(Some(&3), result.discovery_time.get(&5));
+        assert_eq!(Some(&4), result.finish_time.get(&5));
+        assert_eq!(Some(&7), result.discovery_time.get(&3));
+        assert_eq!(Some(&9), result.finish_time.get(&1));
+        assert_eq!(None, result.discovery_time.get(&6));
+        assert_eq!(
+            Err(GraphError::VertexNotFound(7)),
+            graph.dfs_from(&7).map(|_| ())
+        );
+
+        // every key is discovered before and finished after its descendants
+        for key in result.preorder.iter() {
+            assert_eq!(true, result.discovery_time[key] < result.finish_time[key]);
+        }
+    }
+
+    #[test]
+    fn lazy_traversals() {
+        let graph = init_odg();
+
+        let mut bfs = Bfs::new(&graph, &1);
+        assert_eq!(Some(1), bfs.next());
+        assert_eq!(Some(2), bfs.next());
+        assert_eq!(Some(2), bfs.depth(&4));
+        assert_eq!(Some(&2), bfs.parent(&4));
+        assert_eq!(None, bfs.depth(&5));
+        let found = Bfs::new(&graph, &1).find(|key| *key == 4);
+        assert_eq!(Some(4), found);
+        assert_eq!(0, Bfs::new(&graph, &7).count());
+
+        let mut dfs = Dfs::new(&graph, &1);
+        let first_keys: Vec<i32> = dfs.by_ref().take(3).collect();
+        assert_eq!(vec![1, 2, 4], first_keys);
+        assert_eq!(Some(2), dfs.discovery_time(&4));
+        assert_eq!(None, dfs.finish_time(&4));
+        assert_eq!(vec![5, 3], dfs.collect::<Vec<i32>>());
+        assert_eq!(0, Dfs::new(&graph, &7).count());
+
+        // the lazy searches end with the same result as the eager ones
+        let bfs = Bfs::new(&graph, &3).into_result();
+        assert_eq!(graph.bfs_from(&3).unwrap().order, bfs.order);
+        let dfs = Dfs::new(&graph, &3).into_result();
+        assert_eq!(graph.dfs_from(&3).unwrap().postorder, dfs.postorder);
+    }
+
+    #[test]
+    fn dfs_visitor() {
+        let graph = init_odg();
+
+        let mut events: Vec<String> = vec![];
+        let control = graph
+            .dfs_visit(&1, |event| {
+                events.push(format!("{:?}", event));
+                Control::Continue
+            })
+            .unwrap();
+        assert_eq!(Control::Continue, control);
+        let expected_events = vec![
+            "Discover(1)",
+            "TreeEdge(1, 2)",
+            "Discover(2)",
+            "TreeEdge(2, 4)",
+            "Discover(4)",
+            "TreeEdge(4, 5)",
+            "Discover(5)",
+            "BackEdge(5, 2)",
+            "Finish(5)",
+            "Finish(4)",
+            "Finish(2)",
+            "TreeEdge(1, 3)",
+            "Discover(3)",
+            "CrossForwardEdge(3, 4)",
+            "Finish(3)",
+            "Finish(1)",
+        ];
+        assert_eq!(expected_events, events);
+
+        // stop at the first back edge
+        let mut back_edges = vec![];
+        let control = dfs_visit(&graph, &1, |event| match event {
+            DfsEvent::BackEdge(key_from, key_to) => {
+                back_edges.push((*key_from, *key_to));
+                Control::Break
+            }
+            _ => Control::Continue,
+        });
+        assert_eq!(Ok(Control::Break), control);
+        assert_eq!(vec![(5, 2)], back_edges);
+
+        // skip the subtree of 2, so 4 is reached from 3 instead
+        let mut tree_edges = vec![];
+        graph
+            .dfs_visit(&1, |event| match event {
+                DfsEvent::Discover(2) => Control::Prune,
+                DfsEvent::TreeEdge(key_from, key_to) => {
+                    tree_edges.push((*key_from, *key_to));
+                    Control::Continue
+                }
+                _ => Control::Continue,
+            })
+            .unwrap();
+        assert_eq!(vec![(1, 2), (1, 3), (3, 4), (4, 5)], tree_edges);
+
+        assert_eq!(
+            Err(GraphError::VertexNotFound(7)),
+            graph.dfs_visit(&7, |_| Control::Continue)
+        );
+    }
+
+    #[test]
+    fn bfs_visitor() {
+        let graph = init_odg();
+
+        let mut events: Vec<String> = vec![];
+        graph
+            .bfs_visit(&1, |event| {
+                events.push(format!("{:?}", event));
+                Control::Continue
+            })
+            .unwrap();
+        let expected_events = vec![
+            "Discover(1)",
+            "TreeEdge(1, 2)",
+            "Discover(2)",
+            "TreeEdge(1, 3)",
+            "Discover(3)",
+            "Finish(1)",
+            "TreeEdge(2, 4)",
+            "Discover(4)",
+            "Finish(2)",
+            "NonTreeEdge(3, 4)",
+            "Finish(3)",
+            "TreeEdge(4, 5)",
+            "Discover(5)",
+            "Finish(4)",
+            "NonTreeEdge(5, 2)",
+            "Finish(5)",
+        ];
+        assert_eq!(expected_events, events);
+
+        // refuse to go through the edge from 1 to 3 and stop once 5 is found
+        let mut discovered = vec![];
+        let control = graph.bfs_visit(&1, |event| match event {
+            BfsEvent::TreeEdge(1, 3) => Control::Prune,
+            BfsEvent::Discover(key) => {
+                discovered.push(*key);
+                if *key == 5 {
+                    Control::Break
+                } else {
+                    Control::Continue
+                }
+            }
+            _ => Control::Continue,
+        });
+        assert_eq!(Ok(Control::Break), control);
+        assert_eq!(vec![1, 2, 4, 5], discovered);
+    }
+
+    #[test]
+    fn visitors_on_any_kinship() {
+        let mut graph: UndirectedMultigraph<i32, i32> = UndirectedMultigraph::new();
+        for key in [1, 2, 3] {
+            graph = graph.add_vertex(Vertex::new(key)).unwrap();
+        }
+        for (key_a, key_b) in [(1, 2), (2, 3), (3, 1)] {
+            graph = graph.add_edge(Edge::new(key_a, key_b)).unwrap();
+        }
+
+        let mut finished = vec![];
+        dfs_visit(&graph, &2, |event| {
+            if let DfsEvent::Finish(key) = event {
+                finished.push(*key);
+            }
+            Control::Continue
+        })
+        .unwrap();
+        assert_eq!(2, *finished.last().unwrap());
+        assert_sorted_vec_eq(&vec![1, 2, 3], &finished);
+
+        let mut tree_edges = 0;
+        bfs_visit(&graph, &2, |event| {
+            if let BfsEvent::TreeEdge(_, _) = event {
+                tree_edges += 1;
+            }
+            Control::Continue
+        })
+        .unwrap();
+        assert_eq!(2, tree_edges);
+    }
+
+    #[test]
+    fn forests() {
+        let mut graph: BasicUndirectedGraph<i32, i32> = BasicUndirectedGraph::new();
+        for key in [7, 6, 5, 4, 3, 2, 1] {
+            graph = graph.add_vertex(Vertex::new(key)).unwrap();
+        }
+        for (key_a, key_b) in [(1, 2), (2, 3), (3, 1), (5, 4), (6, 6)] {
+            graph = graph.add_edge(Edge::new(key_a, key_b)).unwrap();
+        }
+
+        // only the component of the first vertex is covered by a single traversal
+        assert_eq!(true, graph.bfs().unwrap().edge_count() < 3);
+
+        let expected_components = vec![(1, 0), (2, 0), (3, 0), (4, 1), (5, 1), (6, 2), (7, 3)];
+        for (forest, component) in [graph.bfs_forest().unwrap(), graph.dfs_forest().unwrap()] {
+            assert_eq!(7, forest.vertex_count());
+            assert_eq!(3, forest.edge_count());
+            assert_eq!(true, forest.contains_edge(&4, &5));
+            let mut components: Vec<(i32, usize)> = component.into_iter().collect();
+            components.sort();
+            assert_eq!(expected_components, components);
+        }
+
+        // the trees of a directed graph start from the smallest key not discovered yet
+        let mut graph: OrderedDirectedGraph<i32, i32> = init_odg();
+        graph = graph.add_edge(Edge::new(6, 1)).unwrap();
+        let (forest, component) = graph.dfs_forest().unwrap();
+        assert_eq!(Some(&0), component.get(&5));
+        assert_eq!(Some(&1), component.get(&6));
+        assert_eq!(false, forest.contains_edge(&6, &1));
+        let (forest, component) = graph.bfs_forest().unwrap();
+        assert_eq!(4, forest.edge_count());
+        assert_eq!(Some(&1), component.get(&6));
+
+        let empty: BasicDirectedGraph<i32, i32> = BasicDirectedGraph::new();
+        let (forest, component) = empty.bfs_forest().unwrap();
+        assert_eq!(0, forest.vertex_count());
+        assert_eq!(true, component.is_empty());
+    }
+
+    #[test]
+    fn multi_source_bfs() {
+        let graph = init_odg();
+
+        let result = graph.multi_source_bfs(&[1, 5]).unwrap();
+        assert_eq!(vec![1, 5, 2, 3, 4], result.order);
+        assert_eq!(Some(&0), result.depth.get(&5));
+        assert_eq!(Some(&1), result.depth.get(&2));
+        assert_eq!(Some(&2), result.depth.get(&4));
+        assert_eq!(None, result.parent.get(&5));
+        assert_eq!(Some(vec![1, 2, 4]), result.path_to(&4));
+        assert_eq!(
+            Err(GraphError::VertexNotFound(7)),
+            graph.multi_source_bfs(&[1, 7]).map(|_| ())
+        );
+
+        let order: Vec<i32> = Bfs::with_starts(&graph, &[6, 4, 4]).collect();
+        assert_eq!(vec![6, 4, 5, 2], order);
+    }
+
+    fn init_weighted() -> BasicDirectedGraph<i32, i32, u32> {
+        let mut graph: BasicDirectedGraph<i32, i32, u32> = BasicDirectedGraph::new();
+        for key in 1..=6 {
+            graph = graph.add_vertex(Vertex::new(key)).unwrap();
+        }
+        for (from, to, weight) in [
+            (1, 2, 7),
+            (1, 3, 2),
+            (3, 2, 3),
+            (2, 4, 1),
+            (3, 4, 8),
+            (4, 5, 2),
+        ] {
+            graph = graph.add_edge(Edge::with_weight(from, to, weight)).unwrap();
+        }
+        graph
+    }
+
+    #[test]
+    fn dijkstra() {
+        let graph = init_weighted();
+
+        let paths = graph.dijkstra(&1).unwrap();
+        let mut distances: Vec<(i32, u32)> = paths.distance.clone().into_iter().collect();
+        distances.sort();
+        assert_eq!(vec![(1, 0), (2, 5), (3, 2), (4, 6), (5, 8)], distances);
+        assert_eq!(Some(&3), paths.predecessor.get(&2));
+        assert_eq!(None, paths.predecessor.get(&1));
+        assert_eq!(Some(vec![1, 3, 2, 4, 5]), paths.path_to(&5));
+        assert_eq!(Some(vec![1]), paths.path_to(&1));
+        assert_eq!(None, paths.path_to(&6));
+
+        assert_eq!(Ok(Some((vec![1, 3, 2, 4], 6))), graph.dijkstra_to(&1, &4));
+        assert_eq!(Ok(Some((vec![3], 0))), graph.dijkstra_to(&3, &3));
+        assert_eq!(Ok(None), graph.dijkstra_to(&1, &6));
+        assert_eq!(Ok(None), graph.dijkstra_to(&5, &1));
+        assert_eq!(
+            Err(GraphError::VertexNotFound(7)),
+            graph.dijkstra(&7).map(|_| ())
+        );
+        assert_eq!(
+            Err(GraphError::VertexNotFound(7)),
+            graph.dijkstra_to(&1, &7)
+        );
+
+        let graph: BasicDirectedGraph<i32, i32, i32> = BasicDirectedGraph::new()
+            .add_vertex(Vertex::new(1))
+            .unwrap()
+            .add_vertex(Vertex::new(2))
+            .unwrap()
+            .add_edge(Edge::with_weight(1, 2, -1))
+            .unwrap();
+        assert_eq!(
+            Err(GraphError::NegativeCost(Edge::new(1, 2))),
+            graph.dijkstra(&1).map(|_| ())
+        );
+        assert_eq!(Ok(Some((vec![2], 0))), graph.dijkstra_to(&2, &2));
+
+        // the edge back to 1 can't shorten any path, but it's still rejected once 2 is settled
+        let graph: BasicDirectedGraph<i32, i32, i32> = BasicDirectedGraph::new()
+            .add_vertex(Vertex::new(1))
+            .unwrap()
+            .add_vertex(Vertex::new(2))
+            .unwrap()
+            .add_vertex(Vertex::new(3))
+            .unwrap()
+            .add_edge(Edge::with_weight(1, 2, 1))
+            .unwrap()
+            .add_edge(Edge::with_weight(2, 1, -1))
+            .unwrap()
+            .add_edge(Edge::with_weight(2, 3, 1))
+            .unwrap();
+        assert_eq!(
+            Err(GraphError::NegativeCost(Edge::new(2, 1))),
+            graph.dijkstra(&1).map(|_| ())
+        );
+        assert_eq!(
+            Err(GraphError::NegativeCost(Edge::new(2, 1))),
+            graph.dijkstra_to(&1, &3)
+        );
+        assert_eq!(Ok(Some((vec![1, 2], 1))), graph.dijkstra_to(&1, &2));
+    }
+
+    #[test]
+    fn dijkstra_with_cost() {
+        // the cost of an edge is the value of the vertex it leads to
+        let mut graph: BasicDirectedGraph<i32, u32> = BasicDirectedGraph::new();
+        for (key, value) in [(1, 0), (2, 10), (3, 1), (4, 1)] {
+            graph = graph.add_vertex(Vertex::with_value(key, value)).unwrap();
+        }
+        for (from, to) in [(1, 2), (1, 3), (3, 4), (2, 4)] {
+            graph = graph.add_edge(Edge::new(from, to)).unwrap();
+        }
+
+        let paths = graph.dijkstra_with(&1, |_, to, _| *to.value()).unwrap();
+        assert_eq!(Some(&10), paths.distance.get(&2));
+        assert_eq!(Some(&2), paths.distance.get(&4));
+        assert_eq!(
+            Ok(Some((vec![1, 3, 4], 2))),
+            graph.dijkstra_to_with(&1, &4, |_, to, _| *to.value())
+        );
+        assert_eq!(
+            Ok(Some((vec![1, 2, 4], 2.0))),
+            graph.dijkstra_to_with(&1, &4, |from, _, _| if *from.key() == 3 {
+                5.0
+            } else {
+                1.0
+            })
+        );
+
+        // the cheapest of the parallel edges is followed, from either end of an undirected edge
+        let mut graph: UndirectedMultigraph<i32, i32, u32> = UndirectedMultigraph::new();
+        for key in 1..=3 {
+            graph.insert_vertex(Vertex::new(key)).unwrap();
+        }
+        for (key_a, key_b, weight) in [(1, 2, 9), (2, 1, 4), (3, 2, 1), (3, 3, 0)] {
+            graph
+                .insert_edge_with_id(Edge::with_weight(key_a, key_b, weight))
+                .unwrap();
+        }
+        assert_eq!(Ok(Some((vec![1, 2, 3], 5))), graph.dijkstra_to(&1, &3));
+        assert_eq!(Ok(Some((vec![3, 2, 1], 5))), graph.dijkstra_to(&3, &1));
+    }
+
+    #[test]
+    fn bellman_ford() {
+        let mut graph: BasicDirectedGraph<i32, i32, i32> = BasicDirectedGraph::new();
+        for key in 1..=6 {
+            graph = graph.add_vertex(Vertex::new(key)).unwrap();
+        }
+        for (from, to, weight) in [
+            (1, 2, 4),
+            (1, 3, 2),
+            (3, 2, -3),
+            (2, 4, 1),
+            (4, 5, -2),
+            (6, 4, 1),
+        ] {
+            graph = graph.add_edge(Edge::with_weight(from, to, weight)).unwrap();
+        }
+
+        let paths = graph.bellman_ford(&1).unwrap();
+        let mut distances: Vec<(i32, i32)> = paths.distance.clone().into_iter().collect();
+        distances.sort();
+        assert_eq!(vec![(1, 0), (2, -1), (3, 2), (4, 0), (5, -2)], distances);
+        assert_eq!(Some(vec![1, 3, 2, 4, 5]), paths.path_to(&5));
+        assert_eq!(None, paths.path_to(&6));
+        assert_eq!(
+            Err(GraphError::NegativeCost(Edge::new(3, 2))),
+            graph.dijkstra(&1).map(|_| ())
+        );
+        assert_eq!(
+            Err(GraphError::VertexNotFound(7)),
+            graph.bellman_ford(&7).map(|_| ())
+        );
+
+        // the cycle is only reported when it is reachable
+        let graph = graph.add_edge(Edge::with_weight(5, 6, 2)).unwrap();
+        assert_eq!(true, graph.bellman_ford(&6).is_ok());
+        let graph = graph
+            .add_edge(Edge::with_weight(4, 6, -2))
+            .unwrap()
+            .add_vertex(Vertex::new(7))
+            .unwrap();
+        assert_eq!(true, graph.bellman_ford(&7).is_ok());
+        assert_eq!(
+            Err(GraphError::NegativeCycle(vec![4, 6])),
+            graph.bellman_ford(&1).map(|_| ())
+        );
+        assert_eq!(
+            Err(GraphError::NegativeCycle(vec![4, 6])),
+            graph.bellman_ford(&5).map(|_| ())
+        );
+        assert_eq!(
+            "negative cycle through [4, 6]",
+            graph.bellman_ford(&1).unwrap_err().to_string()
+        );
+
+        // an unweighted graph gets its costs from a closure
+        let graph = init_odg();
+        let paths = graph.bellman_ford_with(&1, |_, _, _| 1).unwrap();
+        assert_eq!(Some(&3), paths.distance.get(&5));
+        assert_eq!(
+            Err(GraphError::NegativeCycle(vec![2, 4, 5])),
+            graph.bellman_ford_with(&1, |_, _, _| -1).map(|_| ())
+        );
+        let graph: BasicUndirectedGraph<i32, i32, i32> = BasicUndirectedGraph::new()
+            .add_vertex(Vertex::new(1))
+            .unwrap()
+            .add_vertex(Vertex::new(2))
+            .unwrap()
+            .add_edge(Edge::with_weight(2, 1, -1))
+            .unwrap();
+        assert_eq!(
+            Err(GraphError::NegativeCycle(vec![1, 2])),
+            graph.bellman_ford(&1).map(|_| ())
+        );
+    }
+
+    fn init_grid() -> BasicUndirectedGraph<(i32, i32), i32> {
+        // a 5 by 5 grid with a wall at x = 2, open only at y = 4
+        let mut graph: BasicUndirectedGraph<(i32, i32), i32> = BasicUndirectedGraph::new();
+        for x in 0..5 {
+            for y in 0..5 {
+                graph = graph.add_vertex(Vertex::new((x, y))).unwrap();
+            }
+        }
+        for x in 0..5 {
+            for y in 0..5 {
+                if x < 4 && (y == 4 || (x != 1 && x != 2)) {
+                    graph = graph.add_edge(Edge::new((x, y), (x + 1, y))).unwrap();
+                }
+                if y < 4 && x != 2 {
+                    graph = graph.add_edge(Edge::new((x, y), (x, y + 1))).unwrap();
+                }
+            }
+        }
+        graph
+    }
+
+    #[test]
+    fn astar() {
+        let graph = init_grid();
+        let manhattan = |from: &Vertex<(i32, i32), i32>, to: &Vertex<(i32, i32), i32>| {
+            (from.key().0 - to.key().0).abs() + (from.key().1 - to.key().1).abs()
+        };
+
+        let result = graph
+            .astar(&(0, 0), &(4, 0), |_, _, _| 1, manhattan)
+            .unwrap();
+        let (path, cost) = result.path.unwrap();
+        assert_eq!(12, cost);
+        assert_eq!(13, path.len());
+        assert_eq!(Some(&(0, 0)), path.first());
+        assert_eq!(Some(&(2, 4)), path.get(6));
+        assert_eq!(Some(&(4, 0)), path.last());
+        let blind = graph
+            .astar(&(0, 0), &(4, 0), |_, _, _| 1, |_, _| 0)
+            .unwrap();
+        assert_eq!(Some(12), blind.path.map(|(_, cost)| cost));
+
+        // a heuristic close to the real cost spares expansions
+        let result = graph
+            .astar(&(0, 0), &(0, 4), |_, _, _| 1, manhattan)
+            .unwrap();
+        let blind = graph
+            .astar(&(0, 0), &(0, 4), |_, _, _| 1, |_, _| 0)
+            .unwrap();
+        assert_eq!(result.path, blind.path);
+        assert_eq!(4, result.expanded);
+        assert_eq!(true, result.expanded < blind.expanded);
+
+        let result = graph
+            .astar(&(3, 3), &(3, 3), |_, _, _| 1, manhattan)
+            .unwrap();
+        assert_eq!(Some((vec![(3, 3)], 0)), result.path);
+        assert_eq!(0, result.expanded);
+        let graph = graph.add_vertex(Vertex::new((9, 9))).unwrap();
+        assert_eq!(
+            None,
+            graph
+                .astar(&(0, 0), &(9, 9), |_, _, _| 1, manhattan)
+                .unwrap()
+                .path
+        );
+        assert_eq!(
+            Err(GraphError::VertexNotFound((5, 5))),
+            graph.astar(&(0, 0), &(5, 5), |_, _, _| 1, manhattan)
+        );
+        assert_eq!(
+            Err(GraphError::NegativeCost(Edge::new((0, 0), (0, 1)))),
+            graph
+                .astar(
+                    &(0, 0),
+                    &(0, 1),
+                    |from, to, _| if (from.key(), to.key()) == (&(0, 0), &(0, 1)) {
+                        -1
+                    } else {
+                        1
+                    },
+                    manhattan
+                )
+                .map(|_| ())
+        );
+
+        // the heuristic can read the coordinates stored in the values
+        let mut graph: BasicDirectedGraph<char, (f64, f64), f64> = BasicDirectedGraph::new();
+        for (key, value) in [
+            ('a', (0.0, 0.0)),
+            ('b', (3.0, 4.0)),
+            ('c', (6.0, 0.0)),
+            ('d', (3.0, -1.0)),
+        ] {
+            graph = graph.add_vertex(Vertex::with_value(key, value)).unwrap();
+        }
+        for (from, to, weight) in [
+            ('a', 'b', 5.0),
+            ('b', 'c', 5.0),
+            ('a', 'd', 3.5),
+            ('d', 'c', 3.5),
+        ] {
+            graph = graph.add_edge(Edge::with_weight(from, to, weight)).unwrap();
+        }
+        let euclid = |from: &Vertex<char, (f64, f64)>, to: &Vertex<char, (f64, f64)>| {
+            let (dx, dy) = (from.value().0 - to.value().0, from.value().1 - to.value().1);
+            (dx * dx + dy * dy).sqrt()
+        };
+        let result = graph
+            .astar(&'a', &'c', |_, _, weight| *weight, euclid)
+            .unwrap();
+        assert_eq!(Some((vec!['a', 'd', 'c'], 7.0)), result.path);
+        assert_eq!(2, result.expanded);
+    }
+
+    #[test]
+    fn bidirectional_astar() {
+        let graph = init_grid();
+        let manhattan = |from: &Vertex<(i32, i32), i32>, to: &Vertex<(i32, i32), i32>| {
+            (from.key().0 - to.key().0).abs() + (from.key().1 - to.key().1).abs()
+        };
+
+        let result = graph
+            .bidirectional_astar(&(0, 0), &(4, 0), |_, _, _| 1, manhattan)
+            .unwrap();
+        let (path, cost) = result.path.unwrap();
+        assert_eq!(12, cost);
+        assert_eq!(13, path.len());
+        assert_eq!(Some(&(0, 0)), path.first());
+        assert_eq!(Some(&(4, 0)), path.last());
+        for step in path.windows(2) {
+            assert_eq!(true, graph.contains_edge(&step[0], &step[1]));
+        }
+        let blind = graph
+            .bidirectional_astar(&(0, 0), &(4, 0), |_, _, _| 1, |_, _| 0)
+            .unwrap();
+        assert_eq!(Some(12), blind.path.map(|(_, cost)| cost));
+        assert_eq!(true, result.expanded <= blind.expanded);
+
+        // the backward search follows the edges of a directed graph against their direction
+        let graph = init_odg();
+        let result = graph
+            .bidirectional_astar(&1, &5, |_, _, _| 1, |_, _| 0)
+            .unwrap();
+        let (path, cost) = result.path.unwrap();
+        assert_eq!(3, cost);
+        assert_eq!(true, path == vec![1, 2, 4, 5] || path == vec![1, 3, 4, 5]);
+        assert_eq!(
+            None,
+            graph
+                .bidirectional_astar(&5, &1, |_, _, _| 1, |_, _| 0)
+                .unwrap()
+                .path
+        );
+        assert_eq!(
+            Some((vec![2], 0)),
+            graph
+                .bidirectional_astar(&2, &2, |_, _, _| 1, |_, _| 0)
+                .unwrap()
+                .path
+        );
+        assert_eq!(
+            Err(GraphError::VertexNotFound(7)),
+            graph.bidirectional_astar(&7, &1, |_, _, _| 1, |_, _| 0)
+        );
+    }
+
+    #[test]
+    fn all_pairs_shortest_paths() {
+        let mut graph: BasicDirectedGraph<i32, i32, i32> = BasicDirectedGraph::new();
+        for key in [4, 3, 2, 1] {
+            graph = graph.add_vertex(Vertex::new(key)).unwrap();
+        }
+        for (from, to, weight) in [(1, 2, 4), (1, 3, 2), (3, 2, -3), (2, 4, 1), (4, 1, 3)] {
+            graph = graph.add_edge(Edge::with_weight(from, to, weight)).unwrap();
+        }
+
+        for paths in [graph.floyd_warshall().unwrap(), graph.johnson().unwrap()] {
+            assert_eq!(vec![1, 2, 3, 4], paths.keys);
+            assert_eq!(
+                vec![
+                    vec![Some(0), Some(-1), Some(2), Some(0)],
+                    vec![Some(4), Some(0), Some(6), Some(1)],
+                    vec![Some(1), Some(-3), Some(0), Some(-2)],
+                    vec![Some(3), Some(2), Some(5), Some(0)],
+                ],
+                paths.matrix()
+            );
+            assert_eq!(Some(&-2), paths.distance.get(&(3, 4)));
+            assert_eq!(Some(vec![1, 3, 2, 4]), paths.path(&1, &4));
+            assert_eq!(Some(vec![4, 1, 3, 2]), paths.path(&4, &2));
+            assert_eq!(Some(vec![2]), paths.path(&2, &2));
+            assert_eq!(None, paths.predecessor.get(&(2, 2)));
+        }
+
+        // the pairs without a path are left out
+        let graph = graph.add_vertex(Vertex::new(5)).unwrap();
+        for paths in [graph.floyd_warshall().unwrap(), graph.johnson().unwrap()] {
+            assert_eq!(
+                Some(vec![None, None, None, None, Some(0)]),
+                paths.matrix().pop()
+            );
+            assert_eq!(None, paths.path(&1, &5));
+            assert_eq!(None, paths.distance.get(&(5, 1)));
+        }
+
+        let graph = graph.add_edge(Edge::with_weight(2, 3, 2)).unwrap();
+        assert_eq!(
+            Err(GraphError::NegativeCycle(vec![2, 3])),
+            graph.floyd_warshall().map(|_| ())
+        );
+        assert_eq!(
+            Err(GraphError::NegativeCycle(vec![2, 3])),
+            graph.johnson().map(|_| ())
+        );
+
+        // the cheapest of the parallel edges is taken, in both directions when undirected
+        let mut graph: UndirectedMultigraph<i32, i32, f64> = UndirectedMultigraph::new();
+        for key in 1..=3 {
+            graph.insert_vertex(Vertex::new(key)).unwrap();
+        }
+        for (key_a, key_b, weight) in [(1, 2, 2.5), (2, 1, 1.5), (3, 2, 0.5)] {
+            graph
+                .insert_edge_with_id(Edge::with_weight(key_a, key_b, weight))
+                .unwrap();
+        }
+        for paths in [graph.floyd_warshall().unwrap(), graph.johnson().unwrap()] {
+            assert_eq!(Some(&2.0), paths.distance.get(&(3, 1)));
+            assert_eq!(Some(&2.0), paths.distance.get(&(1, 3)));
+            assert_eq!(Some(vec![3, 2, 1]), paths.path(&3, &1));
+        }
+        let paths = init_odg().johnson_with(|_, _, _| 1).unwrap();
+        assert_eq!(Some(&3), paths.distance.get(&(1, 5)));
+
+        // a dense negative cycle is reported before its costs overflow
+        let mut graph: BasicDirectedGraph<i64, (), i32> = BasicDirectedGraph::new();
+        for key in 0..40 {
+            graph = graph.add_vertex(Vertex::new(key)).unwrap();
+        }
+        for from in 0..40 {
+            for to in 0..40 {
+                if from != to {
+                    graph = graph
+                        .add_edge(Edge::with_weight(from, to, -1_000_000))
+                        .unwrap();
+                }
+            }
+        }
+        assert_eq!(
+            Err(GraphError::NegativeCycle(vec![0, 1])),
+            graph.floyd_warshall().map(|_| ())
+        );
+    }
+
+    #[test]
+    fn topological_sort() {
+        // 3 -> 1 -> 4 <- 2, 5 -> 4, 6 alone
+        let mut graph: BasicDirectedGraph<i32, i32> = BasicDirectedGraph::new();
+        for key in [6, 5, 4, 3, 2, 1] {
+            graph = graph.add_vertex(Vertex::new(key)).unwrap();
+        }
+        for (from, to) in [(3, 1), (1, 4), (2, 4), (5, 4)] {
+            graph = graph.add_edge(Edge::new(from, to)).unwrap();
+        }
+
+        let order = graph.topological_sort().unwrap();
+        assert_sorted_vec_eq(&vec![1, 2, 3, 4, 5, 6], &order);
+        let position = |key: i32| order.iter().position(|k| *k == key).unwrap();
+        for edge in graph.edges() {
+            assert_eq!(true, position(*edge.from()) < position(*edge.to()));
+        }
+        assert_eq!(
+            Ok(vec![2, 3, 1, 5, 4, 6]),
+            graph.lexicographic_topological_sort()
+        );
+        assert_eq!(true, graph.is_acyclic());
+
+        let graph = graph.add_edge(Edge::new(4, 3)).unwrap();
+        assert_eq!(false, graph.is_acyclic());
+        for cycle in [
+            graph.topological_sort().unwrap_err(),
+            graph.lexicographic_topological_sort().unwrap_err(),
+        ] {
+            assert_eq!(&[1, 4, 3], cycle.keys());
+            assert_eq!("cycle through [1, 4, 3]", cycle.to_string());
+        }
+
+        let graph = init_odg();
+        assert_eq!(
+            vec![2, 4, 5],
+            graph
+                .lexicographic_topological_sort()
+                .unwrap_err()
+                .into_keys()
+        );
+        assert_eq!(
+            vec![2, 4, 5],
+            graph.topological_sort().unwrap_err().into_keys()
+        );
+
+        // the loops and the parallel edges of a multigraph are taken into account
+        let mut graph: DirectedMultigraph<i32, i32> = DirectedMultigraph::new();
+        for key in 1..=3 {
+            graph.insert_vertex(Vertex::new(key)).unwrap();
+        }
+        for (from, to) in [(2, 1), (2, 1), (1, 3)] {
+            graph.insert_edge_with_id(Edge::new(from, to)).unwrap();
+        }
+        assert_eq!(Ok(vec![2, 1, 3]), graph.topological_sort());
+        assert_eq!(Ok(vec![2, 1, 3]), graph.lexicographic_topological_sort());
+        graph.insert_edge_with_id(Edge::new(3, 3)).unwrap();
+        assert_eq!(vec![3], graph.topological_sort().unwrap_err().into_keys());
+        assert_eq!(
+            vec![3],
+            graph
+                .lexicographic_topological_sort()
+                .unwrap_err()
+                .into_keys()
+        );
+
+        let empty: BasicDirectedGraph<i32, i32> = BasicDirectedGraph::new();
+        assert_eq!(Ok(vec![]), empty.topological_sort());
+    }
+
+    #[test]
+    fn topological_generations() {
+        // 1 -> 2 -> 3, 1 -> 3, 4 -> 3, 5 alone
+        let mut graph: BasicDirectedGraph<i32, i32> = BasicDirectedGraph::new();
+        for key in [5, 4, 3, 2, 1] {
+            graph = graph.add_vertex(Vertex::new(key)).unwrap();
+        }
+        for (from, to) in [(1, 2), (2, 3), (1, 3), (4, 3)] {
+            graph = graph.add_edge(Edge::new(from, to)).unwrap();
+        }
+
+        assert_eq!(
+            Ok(vec![vec![1, 4, 5], vec![2], vec![3]]),
+            graph.topological_generations()
+        );
+        let depths = graph.longest_path_depths().unwrap();
+        let mut depths: Vec<(i32, usize)> = depths.into_iter().collect();
+        depths.sort();
+        assert_eq!(vec![(1, 0), (2, 1), (3, 2), (4, 0), (5, 0)], depths);
+
+        let graph = graph.add_edge(Edge::new(3, 4)).unwrap();
+        assert_eq!(
+            vec![3, 4],
+            graph.topological_generations().unwrap_err().into_keys()
+        );
+        assert_eq!(
+            vec![3, 4],
+            graph.longest_path_depths().unwrap_err().into_keys()
+        );
+
+        // the parallel edges of a multigraph are waited for once each
+        let mut graph: DirectedMultigraph<i32, i32> = DirectedMultigraph::new();
+        for key in 1..=3 {
+            graph.insert_vertex(Vertex::new(key)).unwrap();
+        }
+        for (from, to) in [(1, 3), (1, 3), (2, 3)] {
+            graph.insert_edge_with_id(Edge::new(from, to)).unwrap();
+        }
+        assert_eq!(
+            Ok(vec![vec![1, 2], vec![3]]),
+            graph.topological_generations()
+        );
+
+        let empty: BasicDirectedGraph<i32, i32> = BasicDirectedGraph::new();
+        assert_eq!(Ok(vec![]), empty.topological_generations());
+    }
 }
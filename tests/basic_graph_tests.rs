@@ -1,3 +1,5 @@
+#![allow(clippy::clone_on_copy)]
+
 #[cfg(test)]
 #[macro_use]
 extern crate pretty_assertions;
@@ -7,7 +9,12 @@ mod utils;
 #[cfg(test)]
 mod basic_directed_graph_tests {
     use crate::utils::assert_sorted_vec_eq;
-    use graphific::{AnyGraph, BasicDirectedGraph, Edge, Kinship, Vertex};
+    use graphific::{
+        Algorithms, AnyGraph, AnyGraphMut, BasicDirectedGraph, Edge, GraphError, Kinship, Vertex,
+    };
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::HashMap;
+    use std::hash::BuildHasherDefault;
 
     #[test]
     fn new_basic_directed_graph() {
@@ -397,16 +404,16 @@ mod basic_directed_graph_tests {
         let expected_edges_v2 = vec![e2.clone(), e3.clone()];
         let expected_edges_v3 = vec![];
 
-        assert_sorted_vec_eq(&expected_edges_v1, &successors.get(&v1).unwrap());
-        assert_sorted_vec_eq(&expected_edges_v2, &successors.get(&v2).unwrap());
-        assert_sorted_vec_eq(&expected_edges_v3, &successors.get(&v3).unwrap());
+        assert_sorted_vec_eq(&expected_edges_v1, successors.get(&v1).unwrap());
+        assert_sorted_vec_eq(&expected_edges_v2, successors.get(&v2).unwrap());
+        assert_sorted_vec_eq(&expected_edges_v3, successors.get(&v3).unwrap());
 
         // tests all keys and its corresponding edges
         let successors = graph.successors_as_key_and_edges();
 
-        assert_sorted_vec_eq(&expected_edges_v1, &successors.get(v1.key()).unwrap());
-        assert_sorted_vec_eq(&expected_edges_v2, &successors.get(v2.key()).unwrap());
-        assert_sorted_vec_eq(&expected_edges_v3, &successors.get(v3.key()).unwrap());
+        assert_sorted_vec_eq(&expected_edges_v1, successors.get(v1.key()).unwrap());
+        assert_sorted_vec_eq(&expected_edges_v2, successors.get(v2.key()).unwrap());
+        assert_sorted_vec_eq(&expected_edges_v3, successors.get(v3.key()).unwrap());
     }
 
     #[test]
@@ -441,16 +448,238 @@ mod basic_directed_graph_tests {
         let expected_edges_v2 = vec![e1.clone(), e3.clone()];
         let expected_edges_v3 = vec![e4.clone()];
 
-        assert_sorted_vec_eq(&expected_edges_v1, &predecessors.get(&v1).unwrap());
-        assert_sorted_vec_eq(&expected_edges_v2, &predecessors.get(&v2).unwrap());
-        assert_sorted_vec_eq(&expected_edges_v3, &predecessors.get(&v3).unwrap());
+        assert_sorted_vec_eq(&expected_edges_v1, predecessors.get(&v1).unwrap());
+        assert_sorted_vec_eq(&expected_edges_v2, predecessors.get(&v2).unwrap());
+        assert_sorted_vec_eq(&expected_edges_v3, predecessors.get(&v3).unwrap());
 
         // tests all keys and its corresponding edges
         let predecessors = graph.predecessors_as_key_and_edges();
 
-        assert_sorted_vec_eq(&expected_edges_v1, &predecessors.get(v1.key()).unwrap());
-        assert_sorted_vec_eq(&expected_edges_v2, &predecessors.get(v2.key()).unwrap());
-        assert_sorted_vec_eq(&expected_edges_v3, &predecessors.get(v3.key()).unwrap());
+        assert_sorted_vec_eq(&expected_edges_v1, predecessors.get(v1.key()).unwrap());
+        assert_sorted_vec_eq(&expected_edges_v2, predecessors.get(v2.key()).unwrap());
+        assert_sorted_vec_eq(&expected_edges_v3, predecessors.get(v3.key()).unwrap());
+    }
+
+    #[test]
+    fn neighbors() {
+        let mut graph: BasicDirectedGraph<i32, i32> = BasicDirectedGraph::new();
+        let v1: Vertex<i32, i32> = Vertex::with_value(1, 1);
+        let v2: Vertex<i32, i32> = Vertex::with_value(2, 4);
+        let v3: Vertex<i32, i32> = Vertex::with_value(3, 9);
+        let e1: Edge<i32> = Edge::new(v1.key().clone(), v2.key().clone());
+        let e2: Edge<i32> = Edge::new(v2.key().clone(), v2.key().clone());
+        let e3: Edge<i32> = Edge::new(v3.key().clone(), v1.key().clone());
+
+        // init
+        graph.insert_vertex(v1.clone()).unwrap();
+        graph.insert_vertex(v2.clone()).unwrap();
+        graph.insert_vertex(v3.clone()).unwrap();
+        graph.insert_edge(e1.clone()).unwrap();
+        graph.insert_edge(e2.clone()).unwrap();
+        graph.insert_edge(e3.clone()).unwrap();
+
+        // only the edges from a key lead to its neighbors
+        assert_sorted_vec_eq(&vec![2], &graph.neighbors(&1).unwrap());
+        assert_sorted_vec_eq(&vec![2], &graph.neighbors(&2).unwrap());
+        assert_sorted_vec_eq(&vec![1], &graph.neighbors(&3).unwrap());
+        assert_eq!(None, graph.neighbors(&4));
+
+        assert_sorted_vec_eq(&vec![e1.clone()], &graph.out_edges(&1).unwrap());
+        assert_sorted_vec_eq(&vec![e3.clone()], &graph.in_edges(&1).unwrap());
+        assert_sorted_vec_eq(&vec![e2.clone()], &graph.out_edges(&2).unwrap());
+        assert_sorted_vec_eq(&vec![e1.clone(), e2.clone()], &graph.in_edges(&2).unwrap());
+        assert_eq!(None, graph.out_edges(&4));
+        assert_eq!(None, graph.in_edges(&4));
+
+        // the indices follow the removals
+        graph.remove_vertex_mut(&v1).unwrap();
+        assert_sorted_vec_eq(&vec![], &graph.neighbors(&3).unwrap());
+        assert_sorted_vec_eq(&vec![e2.clone()], &graph.in_edges(&2).unwrap());
+        graph.remove_all_edges_from_key_mut(2).unwrap();
+        assert_sorted_vec_eq(&vec![], &graph.in_edges(&2).unwrap());
+        assert_sorted_vec_eq(&vec![], &graph.edges());
+    }
+
+    #[test]
+    fn queries() {
+        let mut graph: BasicDirectedGraph<i32, i32> = BasicDirectedGraph::new();
+        let v1: Vertex<i32, i32> = Vertex::with_value(1, 1);
+        let v2: Vertex<i32, i32> = Vertex::with_value(2, 4);
+        let v3: Vertex<i32, i32> = Vertex::with_value(3, 9);
+
+        // init
+        assert_eq!(0, graph.vertex_count());
+        assert_eq!(0, graph.edge_count());
+        graph.insert_vertex(v1.clone()).unwrap();
+        graph.insert_vertex(v2.clone()).unwrap();
+        graph.insert_vertex(v3.clone()).unwrap();
+        graph.insert_edge(Edge::new(1, 2)).unwrap();
+        graph.insert_edge(Edge::new(2, 2)).unwrap();
+        graph.insert_edge(Edge::new(3, 1)).unwrap();
+
+        assert_eq!(Some(&v2), graph.get_vertex(&2));
+        assert_eq!(&4, graph.get_vertex(&2).unwrap().value());
+        assert_eq!(None, graph.get_vertex(&4));
+        assert_eq!(true, graph.contains_key(&3));
+        assert_eq!(false, graph.contains_key(&4));
+        assert_eq!(true, graph.contains_edge(&1, &2));
+        assert_eq!(true, graph.contains_edge(&2, &2));
+        assert_eq!(false, graph.contains_edge(&1, &1));
+        assert_eq!(3, graph.vertex_count());
+        assert_eq!(3, graph.edge_count());
+
+        // a loop counts once in each direction
+        assert_eq!(Some(1), graph.in_degree(&1));
+        assert_eq!(Some(1), graph.out_degree(&1));
+        assert_eq!(Some(2), graph.degree(&1));
+        assert_eq!(Some(2), graph.in_degree(&2));
+        assert_eq!(Some(1), graph.out_degree(&2));
+        assert_eq!(Some(3), graph.degree(&2));
+        assert_eq!(Some(0), graph.in_degree(&3));
+        assert_eq!(Some(1), graph.degree(&3));
+        assert_eq!(true, graph.contains_edge(&3, &1));
+        assert_eq!(false, graph.contains_edge(&1, &3));
+        assert_eq!(None, graph.in_degree(&4));
+        assert_eq!(None, graph.out_degree(&4));
+        assert_eq!(None, graph.degree(&4));
+
+        // the counts follow the removals
+        graph.remove_vertex_mut(&v2).unwrap();
+        assert_eq!(2, graph.vertex_count());
+        assert_eq!(1, graph.edge_count());
+        assert_eq!(Some(1), graph.degree(&1));
+        graph.remove_all_edges_mut();
+        assert_eq!(0, graph.edge_count());
+    }
+
+    #[test]
+    fn vertex_values() {
+        let mut graph: BasicDirectedGraph<i32, String> = BasicDirectedGraph::new();
+        let v1: Vertex<i32, String> = Vertex::with_value(1, String::from("one"));
+        let v2: Vertex<i32, String> = Vertex::with_value(2, String::from("two"));
+        let e1: Edge<i32> = Edge::new(v1.key().clone(), v2.key().clone());
+
+        // init
+        graph = graph
+            .add_vertex(v1.clone())
+            .unwrap()
+            .add_vertex(v2.clone())
+            .unwrap()
+            .add_edge(e1.clone())
+            .unwrap();
+
+        // the edges are kept and the old graph is untouched
+        let (new_graph, old_value) = graph.update_vertex_value(1, String::from("uno")).unwrap();
+        assert_eq!("one", old_value);
+        assert_eq!("uno", new_graph.get_vertex(&1).unwrap().value());
+        assert_eq!("one", graph.get_vertex(&1).unwrap().value());
+        assert_sorted_vec_eq(&vec![e1.clone()], &new_graph.edges());
+        assert_eq!(true, graph.update_vertex_value(3, String::new()).is_none());
+
+        let (new_graph, old_value) = new_graph
+            .with_vertex_value(2, |value| value.to_uppercase())
+            .unwrap();
+        assert_eq!("two", old_value);
+        assert_eq!("TWO", new_graph.get_vertex(&2).unwrap().value());
+        assert_eq!(
+            Some(GraphError::VertexNotFound(3)),
+            new_graph
+                .try_with_vertex_value(3, |value| value.clone())
+                .err()
+        );
+
+        let new_graph = new_graph.map_vertex_values(|key, value| format!("{}:{}", key, value));
+        assert_eq!("1:uno", new_graph.get_vertex(&1).unwrap().value());
+        assert_eq!("2:TWO", new_graph.get_vertex(&2).unwrap().value());
+        assert_sorted_vec_eq(&vec![e1.clone()], &new_graph.edges());
+
+        // in place
+        assert_eq!(
+            Ok(String::from("one")),
+            graph.update_vertex_value_mut(1, String::from("1"))
+        );
+        assert_eq!(
+            Ok(String::from("two")),
+            graph.with_vertex_value_mut(2, |value| value.len().to_string())
+        );
+        graph.map_vertex_values_mut(|_, value| value.repeat(2));
+        assert_eq!("11", graph.get_vertex(&1).unwrap().value());
+        assert_eq!("33", graph.get_vertex(&2).unwrap().value());
+        assert_eq!(
+            Err(GraphError::VertexNotFound(3)),
+            graph.update_vertex_value_mut(3, String::new())
+        );
+        assert_eq!(1, graph.edge_count());
+    }
+
+    #[test]
+    fn iterators() {
+        let mut graph: BasicDirectedGraph<i32, i32> = BasicDirectedGraph::new();
+        let v1: Vertex<i32, i32> = Vertex::with_value(1, 1);
+        let v2: Vertex<i32, i32> = Vertex::with_value(2, 4);
+        let v3: Vertex<i32, i32> = Vertex::with_value(3, 9);
+        let e1: Edge<i32> = Edge::new(v1.key().clone(), v2.key().clone());
+        let e2: Edge<i32> = Edge::new(v2.key().clone(), v2.key().clone());
+        let e3: Edge<i32> = Edge::new(v3.key().clone(), v1.key().clone());
+
+        // init
+        graph.insert_vertex(v1.clone()).unwrap();
+        graph.insert_vertex(v2.clone()).unwrap();
+        graph.insert_vertex(v3.clone()).unwrap();
+        graph.insert_edge(e1.clone()).unwrap();
+        graph.insert_edge(e2.clone()).unwrap();
+        graph.insert_edge(e3.clone()).unwrap();
+
+        // the lengths are known before iterating
+        assert_eq!(3, graph.vertices_iter().len());
+        assert_eq!(3, graph.keys().len());
+        assert_eq!(3, graph.edges_iter().len());
+        let mut edges_iter = graph.edges_iter();
+        edges_iter.next();
+        assert_eq!(2, edges_iter.len());
+
+        let expected_vertices = vec![v1.clone(), v2.clone(), v3.clone()];
+        let vertices: Vec<Vertex<i32, i32>> = graph.vertices_iter().cloned().collect();
+        assert_sorted_vec_eq(&expected_vertices, &vertices);
+        let keys: Vec<i32> = graph.keys().cloned().collect();
+        assert_sorted_vec_eq(&vec![1, 2, 3], &keys);
+        let expected_edges = vec![e1.clone(), e2.clone(), e3.clone()];
+        let edges: Vec<Edge<i32>> = graph.edges_iter().cloned().collect();
+        assert_sorted_vec_eq(&expected_edges, &edges);
+
+        let neighbors: Vec<i32> = graph.neighbors_iter(&1).unwrap().cloned().collect();
+        assert_sorted_vec_eq(&vec![2], &neighbors);
+        assert_eq!(1, graph.out_edges_iter(&3).unwrap().len());
+        assert_eq!(2, graph.in_edges_iter(&2).unwrap().len());
+        assert_eq!(0, graph.in_edges_iter(&3).unwrap().len());
+        assert_eq!(true, graph.neighbors_iter(&4).is_none());
+        assert_eq!(true, graph.out_edges_iter(&4).is_none());
+        assert_eq!(true, graph.in_edges_iter(&4).is_none());
+    }
+
+    #[test]
+    fn custom_hasher() {
+        type FixedState = BuildHasherDefault<DefaultHasher>;
+        let mut graph: BasicDirectedGraph<i32, i32, (), FixedState> = BasicDirectedGraph::default();
+        let v1: Vertex<i32, i32> = Vertex::with_value(1, 1);
+        let v2: Vertex<i32, i32> = Vertex::with_value(2, 4);
+        let e1: Edge<i32> = Edge::new(v1.key().clone(), v2.key().clone());
+
+        // init
+        graph.insert_vertex(v1.clone()).unwrap();
+        graph.insert_vertex(v2.clone()).unwrap();
+        graph = graph.add_edge(e1.clone()).unwrap();
+        assert_eq!(true, graph.contains_edge(&1, &2));
+        assert_eq!(true, graph.eq(&graph.clone()));
+
+        // the maps returned use the same hasher
+        let successors: HashMap<Vertex<i32, i32>, Vec<Edge<i32>>, FixedState> = graph.successors();
+        assert_sorted_vec_eq(&vec![e1.clone()], successors.get(&v1).unwrap());
+        let key_vertex_map: HashMap<i32, Vertex<i32, i32>, FixedState> = graph.key_vertex_map();
+        assert_eq!(Some(&v2), key_vertex_map.get(&2));
+
+        let result = graph.bfs().unwrap();
+        assert_eq!(2, result.vertex_count());
     }
 
     #[test]
@@ -504,13 +733,298 @@ mod basic_directed_graph_tests {
         graph2 = graph2.add_edge(e4.clone()).unwrap();
         assert_eq!(true, graph1.eq(&graph2));
         assert_eq!(false, graph1.ne(&graph2));
+
+        // the weights of the edges are compared too
+        let weighted: BasicDirectedGraph<i32, i32, u32> = BasicDirectedGraph::new()
+            .add_vertex(v1.clone())
+            .unwrap()
+            .add_vertex(v2.clone())
+            .unwrap()
+            .add_edge(Edge::with_weight(1, 2, 1))
+            .unwrap();
+        let (reweighted, _) = weighted.update_edge_weight(1, 2, 100).unwrap();
+        assert_eq!(false, weighted.eq(&reweighted));
+        let (restored, _) = reweighted.update_edge_weight(1, 2, 1).unwrap();
+        assert_eq!(true, weighted.eq(&restored));
+    }
+
+    #[test]
+    fn edge_weight() {
+        let mut graph: BasicDirectedGraph<i32, i32, f64> = BasicDirectedGraph::new();
+        let v1: Vertex<i32, i32> = Vertex::with_value(1, 1);
+        let v2: Vertex<i32, i32> = Vertex::with_value(2, 4);
+        let v3: Vertex<i32, i32> = Vertex::with_value(3, 9);
+        let e1: Edge<i32, f64> = Edge::with_weight(v1.key().clone(), v2.key().clone(), 1.5);
+        let e2: Edge<i32, f64> = Edge::with_weight(v2.key().clone(), v3.key().clone(), 2.5);
+
+        // init
+        graph = graph
+            .add_vertex(v1.clone())
+            .unwrap()
+            .add_vertex(v2.clone())
+            .unwrap()
+            .add_vertex(v3.clone())
+            .unwrap()
+            .add_edge(e1.clone())
+            .unwrap()
+            .add_edge(e2.clone())
+            .unwrap();
+
+        assert_eq!(Some(&1.5), graph.edge_weight(v1.key(), v2.key()));
+        assert_eq!(Some(&2.5), graph.edge_weight(v2.key(), v3.key()));
+        assert_eq!(None, graph.edge_weight(v1.key(), v3.key()));
+
+        // replace the weight of e1
+        let (graph, old_weight) = graph.update_edge_weight(1, 2, 4.0).unwrap();
+        assert_eq!(1.5, old_weight);
+        assert_eq!(Some(&4.0), graph.edge_weight(v1.key(), v2.key()));
+
+        // try to replace the weight of a missing edge but fail
+        let should_be_none = graph.update_edge_weight(1, 3, 4.0);
+        assert_eq!(true, should_be_none.is_none());
+
+        // the weights travel with the removed edges
+        let (_, _, removed_edges) = graph.remove_vertex(&v2).unwrap();
+        let mut removed_weights: Vec<f64> = removed_edges
+            .iter()
+            .map(|edge| edge.weight().clone())
+            .collect();
+        removed_weights.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(vec![2.5, 4.0], removed_weights);
+    }
+
+    #[test]
+    fn non_copy_values() {
+        let mut graph: BasicDirectedGraph<i32, String> = BasicDirectedGraph::new();
+        let v1: Vertex<i32, String> = Vertex::with_value(1, String::from("one"));
+        let v2: Vertex<i32, String> = Vertex::with_value(2, String::from("two"));
+        let e1: Edge<i32> = Edge::new(v1.key().clone(), v2.key().clone());
+
+        // init
+        graph = graph
+            .add_vertex(v1.clone())
+            .unwrap()
+            .add_vertex(v2.clone())
+            .unwrap()
+            .add_edge(e1.clone())
+            .unwrap();
+
+        let expected_vertices = vec![v1.clone(), v2.clone()];
+        assert_sorted_vec_eq(&expected_vertices, &graph.vertices());
+
+        // remove v1 by its key and get its value back
+        let (graph, removed_v1, removed_edges) = graph.remove_vertex_where_key(1).unwrap();
+        assert_eq!("one", removed_v1.value());
+        assert_sorted_vec_eq(&vec![e1.clone()], &removed_edges);
+        assert_eq!("two", graph.vertices().first().unwrap().value());
+
+        // edges between keys can be added without any default value
+        let graph = graph.add_vertex(v1.clone()).unwrap();
+        let graph = graph.add_edge_between_keys(2, 1).unwrap();
+        assert_eq!(1, graph.edges().len());
+    }
+
+    #[test]
+    fn non_copy_keys() {
+        let mut graph: BasicDirectedGraph<String, i32> = BasicDirectedGraph::new();
+        let v1: Vertex<String, i32> = Vertex::with_value(String::from("serde"), 1);
+        let v2: Vertex<String, i32> = Vertex::with_value(String::from("serde_json"), 2);
+        let e1: Edge<String> = Edge::new(v2.key().clone(), v1.key().clone());
+
+        // init
+        graph = graph
+            .add_vertex(v1.clone())
+            .unwrap()
+            .add_vertex(v2.clone())
+            .unwrap()
+            .add_edge(e1.clone())
+            .unwrap();
+
+        let expected_vertices = vec![v1.clone(), v2.clone()];
+        assert_sorted_vec_eq(&expected_vertices, &graph.vertices());
+        assert_sorted_vec_eq(&vec![e1.clone()], &graph.edges());
+
+        // remove the edge by its keys
+        let (graph, removed_edge) = graph
+            .remove_edge_where_keys(String::from("serde_json"), String::from("serde"))
+            .unwrap();
+        assert_eq!(e1, removed_edge);
+        assert_eq!(true, graph.edges().is_empty());
+
+        // remove v1 by its key
+        let (graph, removed_v1, _) = graph
+            .remove_vertex_where_key(String::from("serde"))
+            .unwrap();
+        assert_eq!(v1, removed_v1);
+        assert_sorted_vec_eq(&vec![v2.clone()], &graph.vertices());
+    }
+
+    #[test]
+    fn mutable_api() {
+        let mut graph: BasicDirectedGraph<i32, i32> = BasicDirectedGraph::new();
+        let v1: Vertex<i32, i32> = Vertex::with_value(1, 1);
+        let v2: Vertex<i32, i32> = Vertex::with_value(2, 4);
+        let v3: Vertex<i32, i32> = Vertex::with_value(3, 9);
+        let e1: Edge<i32> = Edge::new(v1.key().clone(), v2.key().clone());
+        let e2: Edge<i32> = Edge::new(v2.key().clone(), v3.key().clone());
+        let e3: Edge<i32> = Edge::new(v3.key().clone(), v3.key().clone());
+
+        // insert in place
+        assert_eq!(
+            Err(GraphError::VertexNotFound(1)),
+            graph.insert_edge(e1.clone())
+        );
+        assert_eq!(Ok(()), graph.insert_vertex(v1.clone()));
+        assert_eq!(Ok(()), graph.insert_vertex(v2.clone()));
+        assert_eq!(Ok(()), graph.insert_vertex(v3.clone()));
+        assert_eq!(
+            Err(GraphError::DuplicateVertex(3)),
+            graph.insert_vertex(Vertex::with_value(3, 0))
+        );
+        assert_eq!(Ok(()), graph.insert_edge(e1.clone()));
+        assert_eq!(Ok(()), graph.insert_edge(e2.clone()));
+        assert_eq!(Ok(()), graph.insert_edge_between_keys(3, 3));
+        assert_eq!(
+            Err(GraphError::DuplicateEdge(e1.clone())),
+            graph.insert_edge(e1.clone())
+        );
+        // the reversed edge is another edge
+        assert_eq!(Ok(()), graph.insert_edge(Edge::new(2, 1)));
+        assert_eq!(Ok(Edge::new(2, 1)), graph.remove_edge_where_keys_mut(2, 1));
+
+        let expected_vertices = vec![v1.clone(), v2.clone(), v3.clone()];
+        let expected_edges = vec![e1.clone(), e2.clone(), e3.clone()];
+        assert_sorted_vec_eq(&expected_vertices, &graph.vertices());
+        assert_sorted_vec_eq(&expected_edges, &graph.edges());
+
+        // remove in place and get back what was removed
+        let (removed_v2, removed_edges) = graph.remove_vertex_mut(&v2).unwrap();
+        assert_eq!(v2, removed_v2);
+        assert_sorted_vec_eq(&vec![e1.clone(), e2.clone()], &removed_edges);
+        assert_eq!(
+            Err(GraphError::VertexNotFound(2)),
+            graph.remove_vertex_where_key_mut(2)
+        );
+        assert_sorted_vec_eq(&vec![e3.clone()], &graph.edges());
+
+        assert_eq!(Ok(e3.clone()), graph.remove_edge_mut(&e3));
+        assert_eq!(
+            Err(GraphError::EdgeNotFound(e3.clone())),
+            graph.remove_edge_mut(&e3)
+        );
+
+        let (removed_vertices, removed_edges) = graph.remove_all_vertices_mut();
+        assert_sorted_vec_eq(&vec![v1.clone(), v3.clone()], &removed_vertices);
+        assert_eq!(true, removed_edges.is_empty());
+        assert_eq!(true, graph.eq(&BasicDirectedGraph::new()));
+    }
+
+    #[test]
+    fn persistent_versions() {
+        let mut versions: Vec<BasicDirectedGraph<i32, i32>> = vec![BasicDirectedGraph::new()];
+
+        // keep every intermediate graph
+        for key in 0..100 {
+            let graph = versions
+                .last()
+                .unwrap()
+                .add_vertex(Vertex::new(key))
+                .unwrap();
+            let graph = if key > 0 {
+                graph.add_edge(Edge::new(key - 1, key)).unwrap()
+            } else {
+                graph
+            };
+            versions.push(graph);
+        }
+
+        // the older versions are left untouched
+        for (index, graph) in versions.iter().enumerate() {
+            assert_eq!(index, graph.vertices().len());
+            assert_eq!(index.saturating_sub(1), graph.edges().len());
+        }
+
+        let (graph, _, removed_edges) = versions[100].remove_vertex_where_key(50).unwrap();
+        assert_eq!(2, removed_edges.len());
+        assert_eq!(99, graph.vertices().len());
+        assert_eq!(100, versions[100].vertices().len());
+        assert_eq!(99, versions[100].edges().len());
+    }
+
+    #[test]
+    fn errors() {
+        let mut graph: BasicDirectedGraph<i32, i32> = BasicDirectedGraph::new();
+        let v1: Vertex<i32, i32> = Vertex::with_value(1, 1);
+        let v2: Vertex<i32, i32> = Vertex::with_value(2, 4);
+        let e1: Edge<i32> = Edge::new(v1.key().clone(), v2.key().clone());
+
+        // missing endpoints
+        assert_eq!(
+            Some(GraphError::VertexNotFound(1)),
+            graph.try_add_edge(e1.clone()).err()
+        );
+        graph = graph.try_add_vertex(v1.clone()).unwrap();
+        assert_eq!(
+            Some(GraphError::VertexNotFound(2)),
+            graph.try_add_edge(e1.clone()).err()
+        );
+        graph = graph.try_add_vertex(v2.clone()).unwrap();
+
+        // duplicates
+        assert_eq!(
+            Some(GraphError::DuplicateVertex(2)),
+            graph.try_add_vertex(Vertex::with_value(2, 9)).err()
+        );
+        graph = graph.try_add_edge(e1.clone()).unwrap();
+        assert_eq!(
+            Some(GraphError::DuplicateEdge(e1.clone())),
+            graph.try_add_edge(e1.clone()).err()
+        );
+
+        // missing edges and vertices
+        assert_eq!(
+            Some(GraphError::EdgeNotFound(Edge::new(1, 1))),
+            graph.try_remove_edge_where_keys(1, 1).err()
+        );
+        assert_eq!(
+            Some(GraphError::EdgeNotFound(Edge::new(2, 2))),
+            graph.try_update_edge_weight(2, 2, ()).err()
+        );
+        assert_eq!(
+            Some(GraphError::VertexNotFound(3)),
+            graph.try_remove_vertex_where_key(3).err()
+        );
+        assert_eq!(
+            Some(GraphError::VertexNotFound(3)),
+            graph.try_remove_all_edges_where_key(3).err()
+        );
+
+        // the errors are displayable and compose with `?`
+        let error: Box<dyn std::error::Error> = Box::new(GraphError::VertexNotFound(3));
+        assert_eq!("vertex 3 not found", error.to_string());
+        let remove =
+            |graph: &BasicDirectedGraph<i32, i32>| -> Result<usize, Box<dyn std::error::Error>> {
+                let (graph, _) = graph.try_remove_edge(&e1)?;
+                let (graph, _) = graph.try_remove_edge(&e1)?;
+                Ok(graph.edges().len())
+            };
+        assert_eq!(
+            "edge from 1 to 2 not found",
+            remove(&graph).unwrap_err().to_string()
+        );
     }
 }
 
 #[cfg(test)]
 mod basic_undirected_graph_tests {
     use crate::utils::assert_sorted_vec_eq;
-    use graphific::{AnyGraph, BasicUndirectedGraph, Edge, Kinship, Vertex};
+    use graphific::{
+        Algorithms, AnyGraph, AnyGraphMut, BasicUndirectedGraph, Edge, GraphError, Kinship,
+        UndirectedEdge, Vertex,
+    };
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::HashMap;
+    use std::hash::BuildHasherDefault;
 
     #[test]
     fn new_basic_directed_graph() {
@@ -889,16 +1403,16 @@ mod basic_undirected_graph_tests {
         let expected_edges_v2 = vec![e1.clone(), e2.clone(), e4.clone()];
         let expected_edges_v3 = vec![e3.clone(), e4.clone()];
 
-        assert_sorted_vec_eq(&expected_edges_v1, &successors.get(&v1).unwrap());
-        assert_sorted_vec_eq(&expected_edges_v2, &successors.get(&v2).unwrap());
-        assert_sorted_vec_eq(&expected_edges_v3, &successors.get(&v3).unwrap());
+        assert_sorted_vec_eq(&expected_edges_v1, successors.get(&v1).unwrap());
+        assert_sorted_vec_eq(&expected_edges_v2, successors.get(&v2).unwrap());
+        assert_sorted_vec_eq(&expected_edges_v3, successors.get(&v3).unwrap());
 
         // tests all keys and its corresponding edges
         let successors = graph.successors_as_key_and_edges();
 
-        assert_sorted_vec_eq(&expected_edges_v1, &successors.get(v1.key()).unwrap());
-        assert_sorted_vec_eq(&expected_edges_v2, &successors.get(v2.key()).unwrap());
-        assert_sorted_vec_eq(&expected_edges_v3, &successors.get(v3.key()).unwrap());
+        assert_sorted_vec_eq(&expected_edges_v1, successors.get(v1.key()).unwrap());
+        assert_sorted_vec_eq(&expected_edges_v2, successors.get(v2.key()).unwrap());
+        assert_sorted_vec_eq(&expected_edges_v3, successors.get(v3.key()).unwrap());
     }
 
     #[test]
@@ -933,16 +1447,275 @@ mod basic_undirected_graph_tests {
         let expected_edges_v2 = vec![e1.clone(), e2.clone(), e4.clone()];
         let expected_edges_v3 = vec![e3.clone(), e4.clone()];
 
-        assert_sorted_vec_eq(&expected_edges_v1, &predecessors.get(&v1).unwrap());
-        assert_sorted_vec_eq(&expected_edges_v2, &predecessors.get(&v2).unwrap());
-        assert_sorted_vec_eq(&expected_edges_v3, &predecessors.get(&v3).unwrap());
+        assert_sorted_vec_eq(&expected_edges_v1, predecessors.get(&v1).unwrap());
+        assert_sorted_vec_eq(&expected_edges_v2, predecessors.get(&v2).unwrap());
+        assert_sorted_vec_eq(&expected_edges_v3, predecessors.get(&v3).unwrap());
 
         // tests all keys and its corresponding edges
         let predecessors = graph.predecessors_as_key_and_edges();
 
-        assert_sorted_vec_eq(&expected_edges_v1, &predecessors.get(v1.key()).unwrap());
-        assert_sorted_vec_eq(&expected_edges_v2, &predecessors.get(v2.key()).unwrap());
-        assert_sorted_vec_eq(&expected_edges_v3, &predecessors.get(v3.key()).unwrap());
+        assert_sorted_vec_eq(&expected_edges_v1, predecessors.get(v1.key()).unwrap());
+        assert_sorted_vec_eq(&expected_edges_v2, predecessors.get(v2.key()).unwrap());
+        assert_sorted_vec_eq(&expected_edges_v3, predecessors.get(v3.key()).unwrap());
+    }
+
+    #[test]
+    fn neighbors() {
+        let mut graph: BasicUndirectedGraph<i32, i32> = BasicUndirectedGraph::new();
+        let v1: Vertex<i32, i32> = Vertex::with_value(1, 1);
+        let v2: Vertex<i32, i32> = Vertex::with_value(2, 4);
+        let v3: Vertex<i32, i32> = Vertex::with_value(3, 9);
+        let e1: Edge<i32> = Edge::new(v1.key().clone(), v2.key().clone());
+        let e2: Edge<i32> = Edge::new(v2.key().clone(), v2.key().clone());
+        let e3: Edge<i32> = Edge::new(v1.key().clone(), v3.key().clone());
+
+        // init
+        graph.insert_vertex(v1.clone()).unwrap();
+        graph.insert_vertex(v2.clone()).unwrap();
+        graph.insert_vertex(v3.clone()).unwrap();
+        graph.insert_edge(e1.clone()).unwrap();
+        graph.insert_edge(e2.clone()).unwrap();
+        graph.insert_edge(e3.clone()).unwrap();
+
+        // every edge touching a key leads to one of its neighbors
+        assert_sorted_vec_eq(&vec![2, 3], &graph.neighbors(&1).unwrap());
+        assert_sorted_vec_eq(&vec![1, 2], &graph.neighbors(&2).unwrap());
+        assert_sorted_vec_eq(&vec![1], &graph.neighbors(&3).unwrap());
+        assert_eq!(None, graph.neighbors(&4));
+
+        assert_sorted_vec_eq(&vec![e1.clone(), e3.clone()], &graph.out_edges(&1).unwrap());
+        assert_sorted_vec_eq(&vec![e1.clone(), e3.clone()], &graph.in_edges(&1).unwrap());
+        assert_sorted_vec_eq(&vec![e1.clone(), e2.clone()], &graph.out_edges(&2).unwrap());
+        assert_eq!(None, graph.out_edges(&4));
+        assert_eq!(None, graph.in_edges(&4));
+
+        // the indices follow the removals
+        graph.remove_vertex_mut(&v1).unwrap();
+        assert_sorted_vec_eq(&vec![], &graph.neighbors(&3).unwrap());
+        assert_sorted_vec_eq(&vec![e2.clone()], &graph.out_edges(&2).unwrap());
+        graph.remove_all_edges_from_key_mut(2).unwrap();
+        assert_sorted_vec_eq(&vec![], &graph.neighbors(&2).unwrap());
+        assert_sorted_vec_eq(&vec![], &graph.edges());
+    }
+
+    #[test]
+    fn queries() {
+        let mut graph: BasicUndirectedGraph<i32, i32> = BasicUndirectedGraph::new();
+        let v1: Vertex<i32, i32> = Vertex::with_value(1, 1);
+        let v2: Vertex<i32, i32> = Vertex::with_value(2, 4);
+        let v3: Vertex<i32, i32> = Vertex::with_value(3, 9);
+
+        // init
+        assert_eq!(0, graph.vertex_count());
+        assert_eq!(0, graph.edge_count());
+        graph.insert_vertex(v1.clone()).unwrap();
+        graph.insert_vertex(v2.clone()).unwrap();
+        graph.insert_vertex(v3.clone()).unwrap();
+        graph.insert_edge(Edge::new(1, 2)).unwrap();
+        graph.insert_edge(Edge::new(2, 2)).unwrap();
+        graph.insert_edge(Edge::new(3, 1)).unwrap();
+
+        assert_eq!(Some(&v2), graph.get_vertex(&2));
+        assert_eq!(&4, graph.get_vertex(&2).unwrap().value());
+        assert_eq!(None, graph.get_vertex(&4));
+        assert_eq!(true, graph.contains_key(&3));
+        assert_eq!(false, graph.contains_key(&4));
+        assert_eq!(true, graph.contains_edge(&1, &2));
+        assert_eq!(true, graph.contains_edge(&2, &2));
+        assert_eq!(false, graph.contains_edge(&1, &1));
+        assert_eq!(3, graph.vertex_count());
+        assert_eq!(3, graph.edge_count());
+
+        // a loop counts twice
+        assert_eq!(Some(2), graph.degree(&1));
+        assert_eq!(Some(3), graph.degree(&2));
+        assert_eq!(Some(3), graph.in_degree(&2));
+        assert_eq!(Some(3), graph.out_degree(&2));
+        assert_eq!(Some(1), graph.degree(&3));
+        assert_eq!(true, graph.contains_edge(&3, &1));
+        assert_eq!(true, graph.contains_edge(&1, &3));
+        assert_eq!(None, graph.in_degree(&4));
+        assert_eq!(None, graph.out_degree(&4));
+        assert_eq!(None, graph.degree(&4));
+
+        // the counts follow the removals
+        graph.remove_vertex_mut(&v2).unwrap();
+        assert_eq!(2, graph.vertex_count());
+        assert_eq!(1, graph.edge_count());
+        assert_eq!(Some(1), graph.degree(&1));
+        graph.remove_all_edges_mut();
+        assert_eq!(0, graph.edge_count());
+    }
+
+    #[test]
+    fn vertex_values() {
+        let mut graph: BasicUndirectedGraph<i32, String> = BasicUndirectedGraph::new();
+        let v1: Vertex<i32, String> = Vertex::with_value(1, String::from("one"));
+        let v2: Vertex<i32, String> = Vertex::with_value(2, String::from("two"));
+        let e1: Edge<i32> = Edge::new(v1.key().clone(), v2.key().clone());
+
+        // init
+        graph = graph
+            .add_vertex(v1.clone())
+            .unwrap()
+            .add_vertex(v2.clone())
+            .unwrap()
+            .add_edge(e1.clone())
+            .unwrap();
+
+        // the edges are kept and the old graph is untouched
+        let (new_graph, old_value) = graph.update_vertex_value(1, String::from("uno")).unwrap();
+        assert_eq!("one", old_value);
+        assert_eq!("uno", new_graph.get_vertex(&1).unwrap().value());
+        assert_eq!("one", graph.get_vertex(&1).unwrap().value());
+        assert_sorted_vec_eq(&vec![e1.clone()], &new_graph.edges());
+        assert_eq!(true, graph.update_vertex_value(3, String::new()).is_none());
+
+        let (new_graph, old_value) = new_graph
+            .with_vertex_value(2, |value| value.to_uppercase())
+            .unwrap();
+        assert_eq!("two", old_value);
+        assert_eq!("TWO", new_graph.get_vertex(&2).unwrap().value());
+        assert_eq!(
+            Some(GraphError::VertexNotFound(3)),
+            new_graph
+                .try_with_vertex_value(3, |value| value.clone())
+                .err()
+        );
+
+        let new_graph = new_graph.map_vertex_values(|key, value| format!("{}:{}", key, value));
+        assert_eq!("1:uno", new_graph.get_vertex(&1).unwrap().value());
+        assert_eq!("2:TWO", new_graph.get_vertex(&2).unwrap().value());
+        assert_sorted_vec_eq(&vec![e1.clone()], &new_graph.edges());
+
+        // in place
+        assert_eq!(
+            Ok(String::from("one")),
+            graph.update_vertex_value_mut(1, String::from("1"))
+        );
+        assert_eq!(
+            Ok(String::from("two")),
+            graph.with_vertex_value_mut(2, |value| value.len().to_string())
+        );
+        graph.map_vertex_values_mut(|_, value| value.repeat(2));
+        assert_eq!("11", graph.get_vertex(&1).unwrap().value());
+        assert_eq!("33", graph.get_vertex(&2).unwrap().value());
+        assert_eq!(
+            Err(GraphError::VertexNotFound(3)),
+            graph.update_vertex_value_mut(3, String::new())
+        );
+        assert_eq!(1, graph.edge_count());
+    }
+
+    #[test]
+    fn iterators() {
+        let mut graph: BasicUndirectedGraph<i32, i32> = BasicUndirectedGraph::new();
+        let v1: Vertex<i32, i32> = Vertex::with_value(1, 1);
+        let v2: Vertex<i32, i32> = Vertex::with_value(2, 4);
+        let v3: Vertex<i32, i32> = Vertex::with_value(3, 9);
+        let e1: Edge<i32> = Edge::new(v1.key().clone(), v2.key().clone());
+        let e2: Edge<i32> = Edge::new(v2.key().clone(), v2.key().clone());
+        let e3: Edge<i32> = Edge::new(v1.key().clone(), v3.key().clone());
+
+        // init
+        graph.insert_vertex(v1.clone()).unwrap();
+        graph.insert_vertex(v2.clone()).unwrap();
+        graph.insert_vertex(v3.clone()).unwrap();
+        graph.insert_edge(e1.clone()).unwrap();
+        graph.insert_edge(e2.clone()).unwrap();
+        graph.insert_edge(e3.clone()).unwrap();
+
+        // the lengths are known before iterating
+        assert_eq!(3, graph.vertices_iter().len());
+        assert_eq!(3, graph.keys().len());
+        assert_eq!(3, graph.edges_iter().len());
+        let mut edges_iter = graph.edges_iter();
+        edges_iter.next();
+        assert_eq!(2, edges_iter.len());
+
+        let expected_vertices = vec![v1.clone(), v2.clone(), v3.clone()];
+        let vertices: Vec<Vertex<i32, i32>> = graph.vertices_iter().cloned().collect();
+        assert_sorted_vec_eq(&expected_vertices, &vertices);
+        let keys: Vec<i32> = graph.keys().cloned().collect();
+        assert_sorted_vec_eq(&vec![1, 2, 3], &keys);
+        let expected_edges = vec![e1.clone(), e2.clone(), e3.clone()];
+        let edges: Vec<Edge<i32>> = graph.edges_iter().cloned().collect();
+        assert_sorted_vec_eq(&expected_edges, &edges);
+
+        let neighbors: Vec<i32> = graph.neighbors_iter(&1).unwrap().cloned().collect();
+        assert_sorted_vec_eq(&vec![2, 3], &neighbors);
+        assert_eq!(1, graph.out_edges_iter(&3).unwrap().len());
+        assert_eq!(2, graph.in_edges_iter(&2).unwrap().len());
+        assert_eq!(1, graph.in_edges_iter(&3).unwrap().len());
+        assert_eq!(true, graph.neighbors_iter(&4).is_none());
+        assert_eq!(true, graph.out_edges_iter(&4).is_none());
+        assert_eq!(true, graph.in_edges_iter(&4).is_none());
+    }
+
+    #[test]
+    fn custom_hasher() {
+        type FixedState = BuildHasherDefault<DefaultHasher>;
+        let mut graph: BasicUndirectedGraph<i32, i32, (), FixedState> =
+            BasicUndirectedGraph::default();
+        let v1: Vertex<i32, i32> = Vertex::with_value(1, 1);
+        let v2: Vertex<i32, i32> = Vertex::with_value(2, 4);
+        let e1: Edge<i32> = Edge::new(v1.key().clone(), v2.key().clone());
+
+        // init
+        graph.insert_vertex(v1.clone()).unwrap();
+        graph.insert_vertex(v2.clone()).unwrap();
+        graph = graph.add_edge(e1.clone()).unwrap();
+        assert_eq!(true, graph.contains_edge(&1, &2));
+        assert_eq!(true, graph.eq(&graph.clone()));
+
+        // the maps returned use the same hasher
+        let successors: HashMap<Vertex<i32, i32>, Vec<Edge<i32>>, FixedState> = graph.successors();
+        assert_sorted_vec_eq(&vec![e1.clone()], successors.get(&v1).unwrap());
+        let key_vertex_map: HashMap<i32, Vertex<i32, i32>, FixedState> = graph.key_vertex_map();
+        assert_eq!(Some(&v2), key_vertex_map.get(&2));
+
+        let result = graph.bfs().unwrap();
+        assert_eq!(2, result.vertex_count());
+    }
+
+    #[test]
+    fn edge_orientation() {
+        let mut graph: BasicUndirectedGraph<i32, i32> = BasicUndirectedGraph::new();
+        let mut other: BasicUndirectedGraph<i32, i32> = BasicUndirectedGraph::new();
+        for key in [1, 2, 3] {
+            graph.insert_vertex(Vertex::new(key)).unwrap();
+            other.insert_vertex(Vertex::new(key)).unwrap();
+        }
+        graph.insert_edge(Edge::new(2, 1)).unwrap();
+        graph.insert_edge(Edge::new(3, 2)).unwrap();
+        other.insert_edge(Edge::new(2, 3)).unwrap();
+        other.insert_edge(Edge::new(1, 2)).unwrap();
+
+        // the order of the keys given doesn't matter
+        assert_eq!(true, graph == other);
+        assert_eq!(
+            Err(GraphError::DuplicateEdge(Edge::new(1, 2))),
+            graph.insert_edge(Edge::new(1, 2))
+        );
+        assert_eq!(true, graph.contains_edge(&1, &2));
+        assert_eq!(true, graph.contains_edge(&2, &1));
+
+        // the edges returned go from their smallest key to the other one
+        assert_sorted_vec_eq(&vec![Edge::new(1, 2), Edge::new(2, 3)], &graph.edges());
+        assert_sorted_vec_eq(
+            &vec![UndirectedEdge::new(2, 1), UndirectedEdge::new(3, 2)],
+            &graph.undirected_edges(),
+        );
+        assert_eq!(2, graph.undirected_edges_iter().len());
+        let incident_edges: Vec<UndirectedEdge<i32>> =
+            graph.incident_edges_iter(&2).unwrap().cloned().collect();
+        assert_sorted_vec_eq(
+            &vec![UndirectedEdge::new(1, 2), UndirectedEdge::new(2, 3)],
+            &incident_edges,
+        );
+        assert_eq!(Some(vec![Edge::new(2, 3)]), graph.out_edges(&3));
+        assert_eq!(Ok(Edge::new(2, 3)), graph.remove_edge_where_keys_mut(3, 2));
+        assert_eq!(false, graph == other);
     }
 
     #[test]
@@ -998,5 +1771,286 @@ mod basic_undirected_graph_tests {
             .unwrap();
         assert_eq!(true, graph1.eq(&graph2));
         assert_eq!(false, graph1.ne(&graph2));
+
+        // the weights of the edges are compared too, whatever their orientation
+        let weighted: BasicUndirectedGraph<i32, i32, u32> = BasicUndirectedGraph::new()
+            .add_vertex(v1.clone())
+            .unwrap()
+            .add_vertex(v2.clone())
+            .unwrap()
+            .add_edge(Edge::with_weight(1, 2, 1))
+            .unwrap();
+        let (reweighted, _) = weighted.update_edge_weight(2, 1, 100).unwrap();
+        assert_eq!(false, weighted.eq(&reweighted));
+        let (restored, _) = reweighted.update_edge_weight(2, 1, 1).unwrap();
+        assert_eq!(true, weighted.eq(&restored));
+    }
+
+    #[test]
+    fn edge_weight() {
+        let mut graph: BasicUndirectedGraph<i32, i32, f64> = BasicUndirectedGraph::new();
+        let v1: Vertex<i32, i32> = Vertex::with_value(1, 1);
+        let v2: Vertex<i32, i32> = Vertex::with_value(2, 4);
+        let v3: Vertex<i32, i32> = Vertex::with_value(3, 9);
+        let e1: Edge<i32, f64> = Edge::with_weight(v1.key().clone(), v2.key().clone(), 1.5);
+        let e2: Edge<i32, f64> = Edge::with_weight(v2.key().clone(), v3.key().clone(), 2.5);
+
+        // init
+        graph = graph
+            .add_vertex(v1.clone())
+            .unwrap()
+            .add_vertex(v2.clone())
+            .unwrap()
+            .add_vertex(v3.clone())
+            .unwrap()
+            .add_edge(e1.clone())
+            .unwrap()
+            .add_edge(e2.clone())
+            .unwrap();
+
+        assert_eq!(Some(&1.5), graph.edge_weight(v1.key(), v2.key()));
+        assert_eq!(Some(&2.5), graph.edge_weight(v2.key(), v3.key()));
+        assert_eq!(None, graph.edge_weight(v1.key(), v3.key()));
+
+        // replace the weight of e1
+        let (graph, old_weight) = graph.update_edge_weight(1, 2, 4.0).unwrap();
+        assert_eq!(1.5, old_weight);
+        assert_eq!(Some(&4.0), graph.edge_weight(v1.key(), v2.key()));
+
+        // try to replace the weight of a missing edge but fail
+        let should_be_none = graph.update_edge_weight(1, 3, 4.0);
+        assert_eq!(true, should_be_none.is_none());
+
+        // the weights travel with the removed edges
+        let (_, _, removed_edges) = graph.remove_vertex(&v2).unwrap();
+        let mut removed_weights: Vec<f64> = removed_edges
+            .iter()
+            .map(|edge| edge.weight().clone())
+            .collect();
+        removed_weights.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(vec![2.5, 4.0], removed_weights);
+    }
+
+    #[test]
+    fn non_copy_values() {
+        let mut graph: BasicUndirectedGraph<i32, String> = BasicUndirectedGraph::new();
+        let v1: Vertex<i32, String> = Vertex::with_value(1, String::from("one"));
+        let v2: Vertex<i32, String> = Vertex::with_value(2, String::from("two"));
+        let e1: Edge<i32> = Edge::new(v1.key().clone(), v2.key().clone());
+
+        // init
+        graph = graph
+            .add_vertex(v1.clone())
+            .unwrap()
+            .add_vertex(v2.clone())
+            .unwrap()
+            .add_edge(e1.clone())
+            .unwrap();
+
+        let expected_vertices = vec![v1.clone(), v2.clone()];
+        assert_sorted_vec_eq(&expected_vertices, &graph.vertices());
+
+        // remove v1 by its key and get its value back
+        let (graph, removed_v1, removed_edges) = graph.remove_vertex_where_key(1).unwrap();
+        assert_eq!("one", removed_v1.value());
+        assert_sorted_vec_eq(&vec![e1.clone()], &removed_edges);
+        assert_eq!("two", graph.vertices().first().unwrap().value());
+
+        // edges between keys can be added without any default value
+        let graph = graph.add_vertex(v1.clone()).unwrap();
+        let graph = graph.add_edge_between_keys(2, 1).unwrap();
+        assert_eq!(1, graph.edges().len());
+    }
+
+    #[test]
+    fn non_copy_keys() {
+        let mut graph: BasicUndirectedGraph<String, i32> = BasicUndirectedGraph::new();
+        let v1: Vertex<String, i32> = Vertex::with_value(String::from("serde"), 1);
+        let v2: Vertex<String, i32> = Vertex::with_value(String::from("serde_json"), 2);
+        let e1: Edge<String> = Edge::new(v1.key().clone(), v2.key().clone());
+
+        // init
+        graph = graph
+            .add_vertex(v1.clone())
+            .unwrap()
+            .add_vertex(v2.clone())
+            .unwrap()
+            .add_edge(e1.clone())
+            .unwrap();
+
+        let expected_vertices = vec![v1.clone(), v2.clone()];
+        assert_sorted_vec_eq(&expected_vertices, &graph.vertices());
+        assert_sorted_vec_eq(&vec![e1.clone()], &graph.edges());
+
+        // remove the edge by its keys
+        let (graph, removed_edge) = graph
+            .remove_edge_where_keys(String::from("serde_json"), String::from("serde"))
+            .unwrap();
+        assert_eq!(e1, removed_edge);
+        assert_eq!(true, graph.edges().is_empty());
+
+        // remove v1 by its key
+        let (graph, removed_v1, _) = graph
+            .remove_vertex_where_key(String::from("serde"))
+            .unwrap();
+        assert_eq!(v1, removed_v1);
+        assert_sorted_vec_eq(&vec![v2.clone()], &graph.vertices());
+    }
+
+    #[test]
+    fn mutable_api() {
+        let mut graph: BasicUndirectedGraph<i32, i32> = BasicUndirectedGraph::new();
+        let v1: Vertex<i32, i32> = Vertex::with_value(1, 1);
+        let v2: Vertex<i32, i32> = Vertex::with_value(2, 4);
+        let v3: Vertex<i32, i32> = Vertex::with_value(3, 9);
+        let e1: Edge<i32> = Edge::new(v1.key().clone(), v2.key().clone());
+        let e2: Edge<i32> = Edge::new(v2.key().clone(), v3.key().clone());
+        let e3: Edge<i32> = Edge::new(v3.key().clone(), v3.key().clone());
+
+        // insert in place
+        assert_eq!(
+            Err(GraphError::VertexNotFound(1)),
+            graph.insert_edge(e1.clone())
+        );
+        assert_eq!(Ok(()), graph.insert_vertex(v1.clone()));
+        assert_eq!(Ok(()), graph.insert_vertex(v2.clone()));
+        assert_eq!(Ok(()), graph.insert_vertex(v3.clone()));
+        assert_eq!(
+            Err(GraphError::DuplicateVertex(3)),
+            graph.insert_vertex(Vertex::with_value(3, 0))
+        );
+        assert_eq!(Ok(()), graph.insert_edge(e1.clone()));
+        assert_eq!(Ok(()), graph.insert_edge(e2.clone()));
+        assert_eq!(Ok(()), graph.insert_edge_between_keys(3, 3));
+        assert_eq!(
+            Err(GraphError::DuplicateEdge(e1.clone())),
+            graph.insert_edge(e1.clone())
+        );
+        // the reversed edge is the same edge
+        assert_eq!(
+            Err(GraphError::DuplicateEdge(Edge::new(2, 1))),
+            graph.insert_edge(Edge::new(2, 1))
+        );
+
+        let expected_vertices = vec![v1.clone(), v2.clone(), v3.clone()];
+        let expected_edges = vec![e1.clone(), e2.clone(), e3.clone()];
+        assert_sorted_vec_eq(&expected_vertices, &graph.vertices());
+        assert_sorted_vec_eq(&expected_edges, &graph.edges());
+
+        // remove in place and get back what was removed
+        let (removed_v2, removed_edges) = graph.remove_vertex_mut(&v2).unwrap();
+        assert_eq!(v2, removed_v2);
+        assert_sorted_vec_eq(&vec![e1.clone(), e2.clone()], &removed_edges);
+        assert_eq!(
+            Err(GraphError::VertexNotFound(2)),
+            graph.remove_vertex_where_key_mut(2)
+        );
+        assert_sorted_vec_eq(&vec![e3.clone()], &graph.edges());
+
+        assert_eq!(Ok(e3.clone()), graph.remove_edge_mut(&e3));
+        assert_eq!(
+            Err(GraphError::EdgeNotFound(e3.clone())),
+            graph.remove_edge_mut(&e3)
+        );
+
+        let (removed_vertices, removed_edges) = graph.remove_all_vertices_mut();
+        assert_sorted_vec_eq(&vec![v1.clone(), v3.clone()], &removed_vertices);
+        assert_eq!(true, removed_edges.is_empty());
+        assert_eq!(true, graph.eq(&BasicUndirectedGraph::new()));
+    }
+
+    #[test]
+    fn persistent_versions() {
+        let mut versions: Vec<BasicUndirectedGraph<i32, i32>> = vec![BasicUndirectedGraph::new()];
+
+        // keep every intermediate graph
+        for key in 0..100 {
+            let graph = versions
+                .last()
+                .unwrap()
+                .add_vertex(Vertex::new(key))
+                .unwrap();
+            let graph = if key > 0 {
+                graph.add_edge(Edge::new(key - 1, key)).unwrap()
+            } else {
+                graph
+            };
+            versions.push(graph);
+        }
+
+        // the older versions are left untouched
+        for (index, graph) in versions.iter().enumerate() {
+            assert_eq!(index, graph.vertices().len());
+            assert_eq!(index.saturating_sub(1), graph.edges().len());
+        }
+
+        let (graph, _, removed_edges) = versions[100].remove_vertex_where_key(50).unwrap();
+        assert_eq!(2, removed_edges.len());
+        assert_eq!(99, graph.vertices().len());
+        assert_eq!(100, versions[100].vertices().len());
+        assert_eq!(99, versions[100].edges().len());
+    }
+
+    #[test]
+    fn errors() {
+        let mut graph: BasicUndirectedGraph<i32, i32> = BasicUndirectedGraph::new();
+        let v1: Vertex<i32, i32> = Vertex::with_value(1, 1);
+        let v2: Vertex<i32, i32> = Vertex::with_value(2, 4);
+        let e1: Edge<i32> = Edge::new(v1.key().clone(), v2.key().clone());
+
+        // missing endpoints
+        assert_eq!(
+            Some(GraphError::VertexNotFound(1)),
+            graph.try_add_edge(e1.clone()).err()
+        );
+        graph = graph.try_add_vertex(v1.clone()).unwrap();
+        assert_eq!(
+            Some(GraphError::VertexNotFound(2)),
+            graph.try_add_edge(e1.clone()).err()
+        );
+        graph = graph.try_add_vertex(v2.clone()).unwrap();
+
+        // duplicates
+        assert_eq!(
+            Some(GraphError::DuplicateVertex(2)),
+            graph.try_add_vertex(Vertex::with_value(2, 9)).err()
+        );
+        graph = graph.try_add_edge(e1.clone()).unwrap();
+        assert_eq!(
+            Some(GraphError::DuplicateEdge(e1.clone())),
+            graph.try_add_edge(e1.clone()).err()
+        );
+
+        // missing edges and vertices
+        assert_eq!(
+            Some(GraphError::EdgeNotFound(Edge::new(1, 1))),
+            graph.try_remove_edge_where_keys(1, 1).err()
+        );
+        assert_eq!(
+            Some(GraphError::EdgeNotFound(Edge::new(2, 2))),
+            graph.try_update_edge_weight(2, 2, ()).err()
+        );
+        assert_eq!(
+            Some(GraphError::VertexNotFound(3)),
+            graph.try_remove_vertex_where_key(3).err()
+        );
+        assert_eq!(
+            Some(GraphError::VertexNotFound(3)),
+            graph.try_remove_all_edges_where_key(3).err()
+        );
+
+        // the errors are displayable and compose with `?`
+        let error: Box<dyn std::error::Error> = Box::new(GraphError::VertexNotFound(3));
+        assert_eq!("vertex 3 not found", error.to_string());
+        let remove =
+            |graph: &BasicUndirectedGraph<i32, i32>| -> Result<usize, Box<dyn std::error::Error>> {
+                let (graph, _) = graph.try_remove_edge(&e1)?;
+                let (graph, _) = graph.try_remove_edge(&e1)?;
+                Ok(graph.edges().len())
+            };
+        assert_eq!(
+            "edge from 1 to 2 not found",
+            remove(&graph).unwrap_err().to_string()
+        );
     }
 }
@@ -0,0 +1,358 @@
+#![allow(clippy::clone_on_copy)]
+
+#[cfg(test)]
+#[macro_use]
+extern crate pretty_assertions;
+
+mod utils;
+
+#[cfg(test)]
+mod directed_multigraph_tests {
+    use crate::utils::assert_sorted_vec_eq;
+    use graphific::{
+        Algorithms, AnyGraph, AnyGraphMut, DirectedMultigraph, Edge, EdgeId, GraphError, Kinship,
+        Multigraph, Vertex,
+    };
+
+    fn init() -> (DirectedMultigraph<i32, i32, i32>, Vec<EdgeId>) {
+        let mut graph: DirectedMultigraph<i32, i32, i32> = DirectedMultigraph::new();
+        for key in 1..=4 {
+            graph.insert_vertex(Vertex::new(key)).unwrap();
+        }
+        let ids = [(1, 2, 10), (1, 2, 20), (2, 3, 5), (3, 3, 1), (1, 2, 30)]
+            .iter()
+            .map(|(key_from, key_to, weight)| {
+                graph
+                    .insert_edge_with_id(Edge::with_weight(*key_from, *key_to, *weight))
+                    .unwrap()
+            })
+            .collect();
+        (graph, ids)
+    }
+
+    #[test]
+    fn new_directed_multigraph() {
+        let graph: DirectedMultigraph<i32, i32> = DirectedMultigraph::new();
+        let vertices: Vec<Vertex<i32, i32>> = vec![];
+        let edges: Vec<Edge<i32>> = vec![];
+        assert_sorted_vec_eq(&vertices, &graph.vertices());
+        assert_sorted_vec_eq(&edges, &graph.edges());
+    }
+
+    #[test]
+    fn parallel_edges() {
+        let (graph, ids) = init();
+
+        assert_eq!(5, graph.edge_count());
+        assert_eq!(5, graph.edges_iter().len());
+        assert_eq!(vec![ids[0], ids[1], ids[4]], graph.edge_ids_between(&1, &2));
+        assert_eq!(Vec::<EdgeId>::new(), graph.edge_ids_between(&2, &1));
+        assert_eq!(Some(&20), graph.edge(ids[1]).map(|edge| edge.weight()));
+        assert_eq!(Some(&10), graph.edge_weight(&1, &2));
+        assert_eq!(Some(vec![2, 2, 2]), graph.neighbors(&1));
+
+        let weights: Vec<i32> = graph
+            .out_edges_iter(&1)
+            .unwrap()
+            .map(|edge| edge.weight().clone())
+            .collect();
+        assert_eq!(vec![10, 20, 30], weights);
+    }
+
+    #[test]
+    fn edge_ids() {
+        let (graph, ids) = init();
+
+        let mut sorted_ids = ids.clone();
+        sorted_ids.sort();
+        assert_eq!(ids, sorted_ids);
+
+        let (graph, removed_edge) = graph.try_remove_edge_by_id(ids[1]).unwrap();
+        assert_eq!(&20, removed_edge.weight());
+        let (graph, id) = graph
+            .try_add_edge_with_id(Edge::with_weight(1, 2, 40))
+            .unwrap();
+        assert_eq!(true, id > ids[4]);
+        assert_eq!(Some(&10), graph.edge(ids[0]).map(|edge| edge.weight()));
+        assert_eq!(vec![ids[0], ids[4], id], graph.edge_ids_between(&1, &2));
+
+        let mut ids_iter: Vec<EdgeId> = graph.edges_with_ids_iter().map(|(id, _)| id).collect();
+        ids_iter.sort();
+        assert_eq!(vec![ids[0], ids[2], ids[3], ids[4], id], ids_iter);
+    }
+
+    #[test]
+    fn fresh_ids_after_removing_all_vertices() {
+        let (graph, ids) = init();
+
+        let (new_graph, vertices, edges) = graph.remove_all_vertices().unwrap();
+        assert_eq!(4, vertices.len());
+        assert_eq!(5, edges.len());
+        let (new_graph, id) = new_graph
+            .add_vertex(Vertex::new(1))
+            .unwrap()
+            .try_add_edge_with_id(Edge::with_weight(1, 1, 0))
+            .unwrap();
+        assert_eq!(true, id > ids[4]);
+        assert_eq!(None, new_graph.edge(ids[0]));
+    }
+
+    #[test]
+    fn degrees() {
+        let (graph, _) = init();
+
+        assert_eq!(Some(3), graph.out_degree(&1));
+        assert_eq!(Some(3), graph.in_degree(&2));
+        assert_eq!(Some(1), graph.out_degree(&2));
+        assert_eq!(Some(2), graph.in_degree(&3));
+        assert_eq!(Some(3), graph.degree(&3));
+        assert_eq!(Some(0), graph.degree(&4));
+        assert_eq!(None, graph.degree(&5));
+    }
+
+    #[test]
+    fn remove_edges() {
+        let (mut graph, ids) = init();
+
+        assert_eq!(
+            Ok(Edge::with_weight(1, 2, 10)),
+            graph.remove_edge_where_keys_mut(1, 2)
+        );
+        assert_eq!(Some(&20), graph.edge_weight(&1, &2));
+        assert_eq!(Ok(20), graph.update_edge_weight_mut(1, 2, 25));
+        assert_eq!(Some(&25), graph.edge(ids[1]).map(|edge| edge.weight()));
+
+        let (new_graph, removed_edges) = graph.try_remove_all_edges_where_keys(1, 2).unwrap();
+        let weights: Vec<i32> = removed_edges.iter().map(|e| e.weight().clone()).collect();
+        assert_eq!(vec![25, 30], weights);
+        assert_eq!(false, new_graph.contains_edge(&1, &2));
+        assert_eq!(2, new_graph.edge_count());
+        assert_eq!(true, graph.contains_edge(&1, &2));
+        assert_eq!(
+            Err(GraphError::EdgeNotFound(Edge::new(1, 2))),
+            new_graph.try_remove_all_edges_where_keys(1, 2).map(|_| ())
+        );
+
+        let (new_graph, _, removed_edges) = graph.try_remove_vertex_where_key(3).unwrap();
+        assert_eq!(2, removed_edges.len());
+        assert_eq!(2, new_graph.edge_count());
+        assert_eq!(Some(2), new_graph.out_degree(&1));
+        assert_eq!(Some(0), new_graph.out_degree(&2));
+    }
+
+    #[test]
+    fn errors() {
+        let (mut graph, ids) = init();
+
+        assert_eq!(
+            Err(GraphError::VertexNotFound(5)),
+            graph.insert_edge_with_id(Edge::new(1, 5))
+        );
+        graph.remove_edge_by_id_mut(ids[0]).unwrap();
+        assert_eq!(
+            Err(GraphError::EdgeIdNotFound(ids[0])),
+            graph.remove_edge_by_id_mut(ids[0])
+        );
+        assert_eq!(
+            Err(GraphError::EdgeIdNotFound(ids[0])),
+            graph.update_edge_weight_by_id_mut(ids[0], 0)
+        );
+        assert_eq!(
+            format!("edge #{} not found", ids[0].index()),
+            GraphError::<i32>::EdgeIdNotFound(ids[0]).to_string()
+        );
+    }
+
+    #[test]
+    fn eq() {
+        let (graph, ids) = init();
+        let (other, _) = init();
+        assert_eq!(true, graph == other);
+
+        let (new_graph, _) = graph.try_remove_edge_by_id(ids[4]).unwrap();
+        let (new_graph, _) = new_graph
+            .try_add_edge_with_id(Edge::with_weight(1, 2, 30))
+            .unwrap();
+        assert_eq!(false, graph == new_graph);
+
+        // the same edges under the same ids with other weights
+        let (reweighted, _) = graph.try_update_edge_weight_by_id(ids[0], 100).unwrap();
+        assert_eq!(false, graph == reweighted);
+        let (restored, _) = reweighted.try_update_edge_weight_by_id(ids[0], 10).unwrap();
+        assert_eq!(true, graph == restored);
+    }
+
+    #[test]
+    fn traversals() {
+        let (graph, _) = init();
+
+        let expected_edges = vec![Edge::new(1, 2), Edge::new(2, 3)];
+        assert_sorted_vec_eq(
+            &expected_edges,
+            &graph
+                .bfs_with_starting_vertex(graph.get_vertex(&1).unwrap())
+                .unwrap()
+                .edges(),
+        );
+        assert_sorted_vec_eq(
+            &expected_edges,
+            &graph
+                .dfs_with_starting_vertex(graph.get_vertex(&1).unwrap())
+                .unwrap()
+                .edges(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod undirected_multigraph_tests {
+    use crate::utils::assert_sorted_vec_eq;
+    use graphific::{
+        Algorithms, AnyGraph, AnyGraphMut, Edge, EdgeId, GraphError, Kinship, Multigraph,
+        UndirectedMultigraph, Vertex,
+    };
+
+    fn init() -> (UndirectedMultigraph<i32, i32, i32>, Vec<EdgeId>) {
+        let mut graph: UndirectedMultigraph<i32, i32, i32> = UndirectedMultigraph::new();
+        for key in 1..=4 {
+            graph.insert_vertex(Vertex::new(key)).unwrap();
+        }
+        let ids = [(1, 2, 10), (2, 1, 20), (2, 3, 5), (3, 3, 1), (3, 3, 2)]
+            .iter()
+            .map(|(key_from, key_to, weight)| {
+                graph
+                    .insert_edge_with_id(Edge::with_weight(*key_from, *key_to, *weight))
+                    .unwrap()
+            })
+            .collect();
+        (graph, ids)
+    }
+
+    #[test]
+    fn new_undirected_multigraph() {
+        let graph: UndirectedMultigraph<i32, i32> = UndirectedMultigraph::new();
+        let vertices: Vec<Vertex<i32, i32>> = vec![];
+        let edges: Vec<Edge<i32>> = vec![];
+        assert_sorted_vec_eq(&vertices, &graph.vertices());
+        assert_sorted_vec_eq(&edges, &graph.edges());
+    }
+
+    #[test]
+    fn parallel_edges() {
+        let (graph, ids) = init();
+
+        assert_eq!(5, graph.edge_count());
+        assert_eq!(5, graph.edges_iter().len());
+        assert_eq!(vec![ids[0], ids[1]], graph.edge_ids_between(&1, &2));
+        assert_eq!(vec![ids[0], ids[1]], graph.edge_ids_between(&2, &1));
+        assert_eq!(vec![ids[3], ids[4]], graph.edge_ids_between(&3, &3));
+        assert_eq!(Some(&10), graph.edge_weight(&2, &1));
+        assert_eq!(Some(&Edge::new(1, 2)), graph.edge(ids[1]));
+        assert_eq!(Some(vec![2, 2]), graph.neighbors(&1));
+        assert_eq!(Some(vec![1, 1, 3]), graph.neighbors(&2));
+        assert_eq!(Some(vec![2, 3, 3]), graph.neighbors(&3));
+    }
+
+    #[test]
+    fn degrees() {
+        let (graph, _) = init();
+
+        assert_eq!(Some(2), graph.degree(&1));
+        assert_eq!(Some(3), graph.degree(&2));
+        assert_eq!(Some(5), graph.degree(&3));
+        assert_eq!(Some(5), graph.in_degree(&3));
+        assert_eq!(Some(5), graph.out_degree(&3));
+        assert_eq!(Some(0), graph.degree(&4));
+        assert_eq!(None, graph.degree(&5));
+
+        let degree_sum: usize = graph.keys().map(|key| graph.degree(key).unwrap()).sum();
+        assert_eq!(2 * graph.edge_count(), degree_sum);
+    }
+
+    #[test]
+    fn remove_edges() {
+        let (mut graph, ids) = init();
+
+        assert_eq!(
+            Ok(Edge::with_weight(1, 2, 10)),
+            graph.remove_edge_where_keys_mut(2, 1)
+        );
+        assert_eq!(Some(&20), graph.edge_weight(&1, &2));
+        assert_eq!(Ok(1), graph.update_edge_weight_mut(3, 3, 7));
+        assert_eq!(Some(&7), graph.edge(ids[3]).map(|edge| edge.weight()));
+
+        let (new_graph, removed_edges) = graph.try_remove_all_edges_where_keys(3, 3).unwrap();
+        assert_eq!(2, removed_edges.len());
+        assert_eq!(Some(1), new_graph.degree(&3));
+        assert_eq!(false, new_graph.contains_edge(&3, &3));
+        assert_eq!(Some(5), graph.degree(&3));
+        assert_eq!(
+            Err(GraphError::EdgeNotFound(Edge::new(3, 3))),
+            new_graph.try_remove_all_edges_where_keys(3, 3).map(|_| ())
+        );
+
+        let (new_graph, _, removed_edges) = graph.try_remove_vertex_where_key(2).unwrap();
+        assert_eq!(2, removed_edges.len());
+        assert_eq!(2, new_graph.edge_count());
+        assert_eq!(Some(0), new_graph.degree(&1));
+        assert_eq!(Some(4), new_graph.degree(&3));
+    }
+
+    #[test]
+    fn errors() {
+        let (mut graph, ids) = init();
+
+        assert_eq!(
+            Err(GraphError::VertexNotFound(5)),
+            graph.insert_edge_with_id(Edge::new(5, 1))
+        );
+        graph.remove_edge_by_id_mut(ids[3]).unwrap();
+        assert_eq!(
+            Err(GraphError::EdgeIdNotFound(ids[3])),
+            graph.remove_edge_by_id_mut(ids[3])
+        );
+        assert_eq!(
+            Err(GraphError::EdgeNotFound(Edge::new(1, 3))),
+            graph.remove_edge_where_keys_mut(1, 3)
+        );
+    }
+
+    #[test]
+    fn fresh_ids_after_removing_all_vertices() {
+        let (graph, ids) = init();
+
+        let (new_graph, vertices, edges) = graph.remove_all_vertices().unwrap();
+        assert_eq!(4, vertices.len());
+        assert_eq!(5, edges.len());
+        let (new_graph, id) = new_graph
+            .add_vertex(Vertex::new(1))
+            .unwrap()
+            .try_add_edge_with_id(Edge::with_weight(1, 1, 0))
+            .unwrap();
+        assert_eq!(true, id > ids[4]);
+        assert_eq!(None, new_graph.edge(ids[0]));
+    }
+
+    #[test]
+    fn eq() {
+        let (graph, ids) = init();
+        let (other, _) = init();
+        assert_eq!(true, graph == other);
+
+        let (reweighted, _) = graph.try_update_edge_weight_by_id(ids[1], 100).unwrap();
+        assert_eq!(false, graph == reweighted);
+        let (restored, _) = reweighted.try_update_edge_weight_by_id(ids[1], 20).unwrap();
+        assert_eq!(true, graph == restored);
+    }
+
+    #[test]
+    fn traversals() {
+        let (graph, _) = init();
+
+        let bfs = graph
+            .bfs_with_starting_vertex(graph.get_vertex(&1).unwrap())
+            .unwrap();
+        assert_eq!(2, bfs.edge_count());
+        assert_sorted_vec_eq(&vec![1, 2, 3, 4], &bfs.keys().cloned().collect());
+    }
+}
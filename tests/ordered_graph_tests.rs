@@ -0,0 +1,189 @@
+#![allow(clippy::clone_on_copy)]
+
+#[cfg(test)]
+#[macro_use]
+extern crate pretty_assertions;
+
+mod utils;
+
+#[cfg(test)]
+mod ordered_directed_graph_tests {
+    use crate::utils::assert_sorted_vec_eq;
+    use graphific::{
+        AnyGraph, AnyGraphMut, BasicDirectedGraph, Edge, GraphError, Kinship, OrderedDirectedGraph,
+        Vertex,
+    };
+
+    fn init() -> OrderedDirectedGraph<i32, i32> {
+        let mut graph: OrderedDirectedGraph<i32, i32> = OrderedDirectedGraph::new();
+        for key in [5, 3, 1, 4, 2] {
+            graph
+                .insert_vertex(Vertex::with_value(key, key * key))
+                .unwrap();
+        }
+        for (key_from, key_to) in [(4, 1), (1, 5), (1, 2), (3, 3), (2, 1), (1, 3)] {
+            graph.insert_edge(Edge::new(key_from, key_to)).unwrap();
+        }
+        graph
+    }
+
+    #[test]
+    fn new_ordered_directed_graph() {
+        let graph: OrderedDirectedGraph<i32, i32> = OrderedDirectedGraph::new();
+        let vertices: Vec<Vertex<i32, i32>> = vec![];
+        let edges: Vec<Edge<i32>> = vec![];
+        assert_eq!(vertices, graph.vertices());
+        assert_eq!(edges, graph.edges());
+    }
+
+    #[test]
+    fn ordered_vertices_and_edges() {
+        let graph = init();
+
+        let keys: Vec<i32> = graph.keys().cloned().collect();
+        assert_eq!(vec![1, 2, 3, 4, 5], keys);
+        let values: Vec<i32> = graph.vertices_iter().map(|v| v.value().clone()).collect();
+        assert_eq!(vec![1, 4, 9, 16, 25], values);
+
+        let expected_edges = vec![
+            Edge::new(1, 2),
+            Edge::new(1, 3),
+            Edge::new(1, 5),
+            Edge::new(2, 1),
+            Edge::new(3, 3),
+            Edge::new(4, 1),
+        ];
+        assert_eq!(expected_edges, graph.edges());
+        assert_eq!(6, graph.edges_iter().len());
+
+        assert_eq!(Some(vec![2, 3, 5]), graph.neighbors(&1));
+        assert_eq!(
+            Some(vec![Edge::new(2, 1), Edge::new(4, 1)]),
+            graph.in_edges(&1)
+        );
+        let successors = graph.successors_as_key_and_edges();
+        assert_eq!(
+            &vec![Edge::new(1, 2), Edge::new(1, 3), Edge::new(1, 5)],
+            successors.get(&1).unwrap()
+        );
+    }
+
+    #[test]
+    fn insertion_order_does_not_matter() {
+        let graph = init();
+        let mut other: OrderedDirectedGraph<i32, i32> = OrderedDirectedGraph::new();
+        for key in 1..=5 {
+            other = other
+                .add_vertex(Vertex::with_value(key, key * key))
+                .unwrap();
+        }
+        for (key_from, key_to) in [(1, 2), (1, 3), (1, 5), (2, 1), (3, 3), (4, 1)] {
+            other = other.add_edge_between_keys(key_from, key_to).unwrap();
+        }
+
+        assert_eq!(true, graph.eq(&other));
+        assert_eq!(graph.vertices(), other.vertices());
+        assert_eq!(graph.edges(), other.edges());
+
+        // the weights of the edges are compared too
+        let weighted: OrderedDirectedGraph<i32, i32, u32> = OrderedDirectedGraph::new()
+            .add_vertex(Vertex::new(1))
+            .unwrap()
+            .add_edge(Edge::with_weight(1, 1, 1))
+            .unwrap();
+        let (reweighted, _) = weighted.update_edge_weight(1, 1, 100).unwrap();
+        assert_eq!(false, weighted.eq(&reweighted));
+    }
+
+    #[test]
+    fn deterministic_kinship_maps() {
+        let graph = init();
+        let mut other: OrderedDirectedGraph<i32, i32> = OrderedDirectedGraph::new();
+        for key in (1..=5).rev() {
+            other = other
+                .add_vertex(Vertex::with_value(key, key * key))
+                .unwrap();
+        }
+        for (key_from, key_to) in [(4, 1), (3, 3), (2, 1), (1, 5), (1, 3), (1, 2)] {
+            other = other.add_edge_between_keys(key_from, key_to).unwrap();
+        }
+
+        // the maps iterate in the same order whatever the order the graphs were filled in
+        let successors: Vec<(i32, Vec<Edge<i32>>)> =
+            graph.successors_as_key_and_edges().into_iter().collect();
+        let other_successors: Vec<(i32, Vec<Edge<i32>>)> =
+            other.successors_as_key_and_edges().into_iter().collect();
+        assert_eq!(successors, other_successors);
+        let predecessors: Vec<i32> = graph.predecessors().keys().map(|v| *v.key()).collect();
+        let other_predecessors: Vec<i32> = other.predecessors().keys().map(|v| *v.key()).collect();
+        assert_eq!(predecessors, other_predecessors);
+        let keys: Vec<i32> = graph.key_vertex_map().into_keys().collect();
+        let other_keys: Vec<i32> = other.key_vertex_map().into_keys().collect();
+        assert_eq!(keys, other_keys);
+
+        // and the edges of each key are ordered
+        assert_eq!(
+            Some(&vec![Edge::new(2, 1), Edge::new(4, 1)]),
+            graph.predecessors_as_key_and_edges().get(&1)
+        );
+    }
+
+    #[test]
+    fn same_content_as_basic_directed_graph() {
+        let graph = init();
+        let mut basic: BasicDirectedGraph<i32, i32> = BasicDirectedGraph::new();
+        for vertex in graph.vertices_iter() {
+            basic.insert_vertex(vertex.clone()).unwrap();
+        }
+        for edge in graph.edges_iter() {
+            basic.insert_edge(edge.clone()).unwrap();
+        }
+
+        assert_sorted_vec_eq(&basic.vertices(), &graph.vertices());
+        assert_sorted_vec_eq(&basic.edges(), &graph.edges());
+        for key in graph.keys() {
+            assert_eq!(basic.in_degree(key), graph.in_degree(key));
+            assert_eq!(basic.out_degree(key), graph.out_degree(key));
+        }
+    }
+
+    #[test]
+    fn removals() {
+        let graph = init();
+
+        let (new_graph, removed_vertex, removed_edges) = graph.remove_vertex_where_key(1).unwrap();
+        assert_eq!(Vertex::with_value(1, 1), removed_vertex);
+        assert_sorted_vec_eq(
+            &vec![
+                Edge::new(1, 2),
+                Edge::new(1, 3),
+                Edge::new(1, 5),
+                Edge::new(2, 1),
+                Edge::new(4, 1),
+            ],
+            &removed_edges,
+        );
+        assert_eq!(vec![Edge::new(3, 3)], new_graph.edges());
+        assert_eq!(6, graph.edge_count());
+
+        let (new_graph, removed_edges) = graph.remove_all_edges().unwrap();
+        assert_eq!(graph.edges(), removed_edges);
+        assert_eq!(0, new_graph.edge_count());
+        assert_eq!(graph.vertices(), new_graph.vertices());
+
+        let mut graph = graph;
+        assert_eq!(
+            Err(GraphError::EdgeNotFound(Edge::new(5, 1))),
+            graph.remove_edge_where_keys_mut(5, 1)
+        );
+        assert_eq!(Ok(Edge::new(1, 5)), graph.remove_edge_where_keys_mut(1, 5));
+        assert_eq!(
+            Ok(vec![Edge::new(1, 2), Edge::new(1, 3)]),
+            graph.remove_all_edges_from_key_mut(1)
+        );
+        graph.map_vertex_values_mut(|key, _| -key);
+        let values: Vec<i32> = graph.vertices_iter().map(|v| v.value().clone()).collect();
+        assert_eq!(vec![-1, -2, -3, -4, -5], values);
+        assert_eq!(3, graph.edge_count());
+    }
+}
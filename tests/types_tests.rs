@@ -60,6 +60,18 @@ mod vertex_tests {
         assert_eq!(11.0, *v2.value());
         assert_ne!(f32::default(), *v2.value());
     }
+
+    #[test]
+    fn non_copy_value_test() {
+        let mut v1: Vertex<i32, Vec<u8>> = Vertex::with_value(0, vec![1, 2]);
+        let v2: Vertex<i32, String> = Vertex::with_value(1, String::from("name"));
+
+        assert_eq!(vec![1, 2], *v1.value());
+        assert_eq!("name", v2.value());
+
+        v1.set_value(vec![3]);
+        assert_eq!(vec![3], *v1.value());
+    }
 }
 
 #[cfg(test)]
@@ -97,4 +109,84 @@ mod edges_tests {
         assert_eq!(1, *e4.to());
         assert_eq!(v2.key(), e4.to());
     }
+
+    #[test]
+    fn with_weight_test() {
+        let e1: Edge<i32, f32> = Edge::with_weight(0, 1, 10.0);
+        let e2: Edge<i32, f32> = Edge::new(1, 0);
+
+        assert_eq!(0, *e1.from());
+        assert_eq!(1, *e1.to());
+        assert_eq!(10.0, *e1.weight());
+        assert_eq!(f32::default(), *e2.weight());
+    }
+
+    #[test]
+    fn set_weight_test() {
+        let mut e1: Edge<i32, f32> = Edge::new(0, 1);
+        let e2: Edge<i32, f32> = Edge::with_weight(0, 1, 11.0);
+
+        assert_eq!(f32::default(), *e1.weight());
+        e1.set_weight(11.0);
+        assert_eq!(11.0, *e1.weight());
+
+        // the weight is not part of the identity of an edge
+        assert_eq!(e1, e2);
+        assert_eq!(e1, Edge::with_weight(0, 1, 12.0));
+        assert_ne!(e1, Edge::with_weight(1, 0, 11.0));
+    }
+}
+
+#[cfg(test)]
+mod undirected_edges_tests {
+    use graphific::{Edge, UndirectedEdge};
+    use std::collections::HashSet;
+
+    #[test]
+    fn orientation_test() {
+        let e1: UndirectedEdge<i32> = UndirectedEdge::new(1, 2);
+        let e2: UndirectedEdge<i32> = UndirectedEdge::new(2, 1);
+        let e3: UndirectedEdge<i32> = UndirectedEdge::new(2, 3);
+
+        assert_eq!(e1, e2);
+        assert_ne!(e1, e3);
+        assert_eq!((&1, &2), e2.endpoints());
+        assert_eq!(true, e1 < e3);
+        assert_eq!(true, e2 < e3);
+
+        let set: HashSet<UndirectedEdge<i32>> = vec![e1, e2, e3].into_iter().collect();
+        assert_eq!(2, set.len());
+        assert_eq!(true, set.contains(&UndirectedEdge::new(3, 2)));
+    }
+
+    #[test]
+    fn endpoints_test() {
+        let e1: UndirectedEdge<i32, f32> = UndirectedEdge::with_weight(3, 1, 0.5);
+        let e2: UndirectedEdge<i32> = UndirectedEdge::new(4, 4);
+
+        assert_eq!(true, e1.contains(&1));
+        assert_eq!(true, e1.contains(&3));
+        assert_eq!(false, e1.contains(&2));
+        assert_eq!(Some(&1), e1.other(&3));
+        assert_eq!(Some(&3), e1.other(&1));
+        assert_eq!(None, e1.other(&2));
+        assert_eq!(false, e1.is_loop());
+        assert_eq!(true, e2.is_loop());
+        assert_eq!(Some(&4), e2.other(&4));
+    }
+
+    #[test]
+    fn conversion_test() {
+        let mut e1: UndirectedEdge<i32, f32> = Edge::with_weight(3, 1, 0.5).into();
+
+        assert_eq!(0.5, *e1.weight());
+        e1.set_weight(1.5);
+        assert_eq!(1.5, *e1.weight());
+
+        let edge: Edge<i32, f32> = e1.into();
+        assert_eq!(1, *edge.from());
+        assert_eq!(3, *edge.to());
+        assert_eq!(1.5, *edge.weight());
+        assert_eq!(&edge, e1.as_edge());
+    }
 }